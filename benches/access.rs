@@ -0,0 +1,122 @@
+//! Benchmarks of single-element access, compared against a plain `[u8; 64]`.
+//!
+//! These use the unstable `test` crate, and so require a nightly toolchain;
+//! run them with `cargo +nightly bench`.
+
+#![feature(test)]
+
+extern crate test;
+
+use quadboard::index::Index;
+use quadboard::raw_quadboard::RawQuadboard;
+use quadboard::Nibble;
+use test::{black_box, Bencher};
+
+/// A fixed pseudorandom sequence of indices, so that the access pattern
+/// can't be predicted or hoisted out of the loop.
+fn indices() -> [Index; 256] {
+    let mut state = 0x9E37_79B9_7F4A_7C15u64;
+
+    core::array::from_fn(|_| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        Index::try_from((state % 64) as u8).unwrap()
+    })
+}
+
+/// The values written by the `set` benchmarks, one for each of [`indices`].
+fn values() -> [Nibble; 256] {
+    core::array::from_fn(|n| Nibble::try_from((n % 16) as u8).unwrap())
+}
+
+/// A board and an equal array holding a mixture of every nibble.
+fn boards() -> (RawQuadboard, [u8; 64]) {
+    let bytes: [u8; 64] = core::array::from_fn(|i| ((i * 7) % 16) as u8);
+    let nibbles = bytes.map(|byte| Nibble::try_from(byte).unwrap());
+    (RawQuadboard::from_nibbles(nibbles), bytes)
+}
+
+#[bench]
+fn get_quadboard(b: &mut Bencher) {
+    let (board, _) = boards();
+    let indices = indices();
+
+    b.iter(|| {
+        let board = black_box(board);
+        indices
+            .iter()
+            .fold(0u32, |acc, &index| acc + board.get(index).get() as u32)
+    });
+}
+
+#[bench]
+fn get_array(b: &mut Bencher) {
+    let (_, array) = boards();
+    let indices = indices();
+
+    b.iter(|| {
+        let array = black_box(array);
+        indices
+            .iter()
+            .fold(0u32, |acc, &index| acc + array[index.get() as usize] as u32)
+    });
+}
+
+#[bench]
+fn get_chained_quadboard(b: &mut Bencher) {
+    let (board, _) = boards();
+
+    // each index depends on the previous element, so the loads can't be
+    // overlapped or vectorised and the latency of a single access dominates
+    b.iter(|| {
+        let board = black_box(board);
+        (0..256u32).fold(0u8, |index, n| {
+            let value = board.get(Index::try_from(index).unwrap()).get();
+            (index + value + (n % 64) as u8) % 64
+        })
+    });
+}
+
+#[bench]
+fn get_chained_array(b: &mut Bencher) {
+    let (_, array) = boards();
+
+    b.iter(|| {
+        let array = black_box(array);
+        (0..256u32).fold(0u8, |index, n| {
+            let value = array[Index::try_from(index).unwrap().get() as usize];
+            (index + value + (n % 64) as u8) % 64
+        })
+    });
+}
+
+#[bench]
+fn set_quadboard(b: &mut Bencher) {
+    let (mut board, _) = boards();
+    let indices = indices();
+    let values = values();
+
+    b.iter(|| {
+        for (&index, &value) in indices.iter().zip(&values) {
+            board.set(index, value);
+        }
+
+        black_box(&mut board);
+    });
+}
+
+#[bench]
+fn set_array(b: &mut Bencher) {
+    let (_, mut array) = boards();
+    let indices = indices();
+    let values = values();
+
+    b.iter(|| {
+        for (&index, &value) in indices.iter().zip(&values) {
+            array[index.get() as usize] = value.get();
+        }
+
+        black_box(&mut array);
+    });
+}
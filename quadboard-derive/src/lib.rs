@@ -0,0 +1,101 @@
+//! Derive macros for the [`quadboard`](https://docs.rs/quadboard) crate.
+//!
+//! These are re-exported by `quadboard` behind its `derive` feature, and
+//! should not usually be depended on directly.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields};
+
+/// Derives `From<T> for Nibble`, `TryFrom<Nibble> for T` and `NibbleEncoding`
+/// for a fieldless enum `T` of at most 16 variants, each of whose
+/// discriminants must lie between 0 and 15.
+///
+/// The discriminants are used as the encodings of the variants, so they can
+/// be chosen freely with the usual `Variant = value` syntax.
+#[proc_macro_derive(NibbleEncoding)]
+pub fn derive_nibble_encoding(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let name = &input.ident;
+
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "NibbleEncoding can only be derived for enums",
+        ));
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "NibbleEncoding cannot be derived for generic enums",
+        ));
+    }
+
+    if data.variants.len() > 16 {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "NibbleEncoding can only be derived for enums with at most 16 variants",
+        ));
+    }
+
+    if let Some(variant) = data
+        .variants
+        .iter()
+        .find(|variant| !matches!(variant.fields, Fields::Unit))
+    {
+        return Err(Error::new_spanned(
+            variant,
+            "NibbleEncoding can only be derived for fieldless enums",
+        ));
+    }
+
+    let variants: Vec<_> = data.variants.iter().map(|variant| &variant.ident).collect();
+
+    Ok(quote! {
+        const _: () = {
+            #(
+                ::core::assert!(
+                    (#name::#variants as isize) >= 0 && (#name::#variants as isize) < 16,
+                    ::core::concat!(
+                        "the discriminant of ",
+                        ::core::stringify!(#name::#variants),
+                        " does not fit in a nibble",
+                    ),
+                );
+            )*
+        };
+
+        impl ::core::convert::From<#name> for ::quadboard::Nibble {
+            fn from(value: #name) -> Self {
+                unsafe { ::quadboard::Nibble::new_unchecked(value as u8) }
+            }
+        }
+
+        impl ::core::convert::TryFrom<::quadboard::Nibble> for #name {
+            type Error = ::quadboard::codec::InvalidNibbleError;
+
+            fn try_from(value: ::quadboard::Nibble) -> ::core::result::Result<Self, Self::Error> {
+                #(
+                    if value.get() == #name::#variants as u8 {
+                        return ::core::result::Result::Ok(#name::#variants);
+                    }
+                )*
+
+                ::core::result::Result::Err(::quadboard::codec::InvalidNibbleError(value))
+            }
+        }
+
+        impl ::quadboard::codec::NibbleEncoding for #name {
+            const VALID: u16 = 0 #( | (1 << (#name::#variants as u8)) )*;
+        }
+    })
+}
@@ -0,0 +1,201 @@
+//! Quadboards which can be shared between threads without locking.
+
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use halfling::Nibble;
+
+use crate::index::Index;
+use crate::raw_quadboard::RawQuadboard;
+
+/// A [`RawQuadboard`] whose channels are held in four [`AtomicU64`]s.
+///
+/// Each channel is loaded and stored atomically, but a board as a whole is
+/// not: a load which races with a store may observe some channels from
+/// before the store and some from after it. Since channels hold bits rather
+/// than elements, such a *torn* read can produce elements which were never
+/// written, which is usually acceptable for heuristic tables shared between
+/// search threads (as in lazy SMP) but must be accounted for by the caller.
+/// Callers needing consistent snapshots should pair the board with their own
+/// synchronisation, such as a sequence lock.
+#[derive(Debug, Default)]
+pub struct AtomicRawQuadboard {
+    channels: [AtomicU64; 4],
+}
+
+impl From<RawQuadboard> for AtomicRawQuadboard {
+    fn from(value: RawQuadboard) -> Self {
+        Self::new(value)
+    }
+}
+
+impl AtomicRawQuadboard {
+    /// Creates a new [`AtomicRawQuadboard`] holding `board`.
+    pub const fn new(board: RawQuadboard) -> Self {
+        let [c0, c1, c2, c3] = board.into_channels();
+
+        Self {
+            channels: [
+                AtomicU64::new(c0),
+                AtomicU64::new(c1),
+                AtomicU64::new(c2),
+                AtomicU64::new(c3),
+            ],
+        }
+    }
+
+    /// Consumes `self` and returns the board it holds.
+    ///
+    /// Since this takes `self` by value, the result is never torn.
+    pub fn into_inner(self) -> RawQuadboard {
+        RawQuadboard::from_channels(self.channels.map(AtomicU64::into_inner))
+    }
+
+    /// Returns a mutable reference to the channels of `self`, for use when
+    /// it is not shared.
+    pub fn get_mut(&mut self) -> &mut [AtomicU64; 4] {
+        &mut self.channels
+    }
+
+    /// Loads every channel with the given ordering.
+    ///
+    /// The channels are loaded one at a time, so the result may be torn
+    /// if it races with a store.
+    ///
+    /// # Panics
+    /// Panics if `order` is [`Release`](Ordering::Release) or
+    /// [`AcqRel`](Ordering::AcqRel).
+    #[inline(always)]
+    pub fn load(&self, order: Ordering) -> RawQuadboard {
+        RawQuadboard::from_channels([
+            self.channels[0].load(order),
+            self.channels[1].load(order),
+            self.channels[2].load(order),
+            self.channels[3].load(order),
+        ])
+    }
+
+    /// Stores every channel of `board` with the given ordering.
+    ///
+    /// The channels are stored one at a time, so concurrent loads may
+    /// observe a mixture of `board` and the previous contents.
+    ///
+    /// # Panics
+    /// Panics if `order` is [`Acquire`](Ordering::Acquire) or
+    /// [`AcqRel`](Ordering::AcqRel).
+    #[inline(always)]
+    pub fn store(&self, board: RawQuadboard, order: Ordering) {
+        for (channel, value) in self.channels.iter().zip(board.into_channels()) {
+            channel.store(value, order);
+        }
+    }
+
+    /// Atomically loads the channel at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than 4, or if `order` is
+    /// [`Release`](Ordering::Release) or [`AcqRel`](Ordering::AcqRel).
+    #[inline(always)]
+    pub fn load_channel(&self, index: usize, order: Ordering) -> u64 {
+        self.channels[index].load(order)
+    }
+
+    /// Atomically stores `value` to the channel at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than 4, or if `order` is
+    /// [`Acquire`](Ordering::Acquire) or [`AcqRel`](Ordering::AcqRel).
+    #[inline(always)]
+    pub fn store_channel(&self, index: usize, value: u64, order: Ordering) {
+        self.channels[index].store(value, order);
+    }
+
+    /// Reads the element at `index`.
+    ///
+    /// The four bits of the element are loaded from separate channels, so
+    /// the result may be torn if it races with a write to that element.
+    ///
+    /// # Panics
+    /// Panics if `order` is [`Release`](Ordering::Release) or
+    /// [`AcqRel`](Ordering::AcqRel).
+    #[inline(always)]
+    pub fn get(&self, index: Index, order: Ordering) -> Nibble {
+        let value = self
+            .channels
+            .iter()
+            .enumerate()
+            .fold(0, |value, (n, channel)| {
+                value | ((((channel.load(order) >> index.get()) & 1) as u8) << n)
+            });
+
+        unsafe { Nibble::new_unchecked(value) }
+    }
+
+    /// Writes `value` to every index set in `mask`, leaving the other
+    /// elements unchanged.
+    ///
+    /// Each channel is updated with a single atomic read-modify-write, so
+    /// concurrent writes to disjoint masks never interfere with each other,
+    /// although concurrent loads may still observe a partial update.
+    #[inline(always)]
+    pub fn fill_masked(&self, mask: u64, value: Nibble, order: Ordering) {
+        for (n, channel) in self.channels.iter().enumerate() {
+            match (value.get() >> n) & 1 {
+                0 => channel.fetch_and(!mask, order),
+                _ => channel.fetch_or(mask, order),
+            };
+        }
+    }
+
+    /// Writes `value` to the element at `index`.
+    ///
+    /// See [`AtomicRawQuadboard::fill_masked`] for the guarantees this
+    /// provides.
+    #[inline(always)]
+    pub fn set(&self, index: Index, value: Nibble, order: Ordering) {
+        self.fill_masked(1 << index.get(), value, order);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn atomic_board_round_trips() {
+        let mut board = RawQuadboard::default();
+        board.set(Index::MAX, Nibble::TEN);
+
+        let atomic = AtomicRawQuadboard::new(board);
+        assert_eq!(atomic.load(Ordering::Relaxed), board);
+        assert_eq!(atomic.get(Index::MAX, Ordering::Relaxed), Nibble::TEN);
+
+        atomic.set(Index::MIN, Nibble::FIVE, Ordering::Release);
+        assert_eq!(atomic.get(Index::MIN, Ordering::Acquire), Nibble::FIVE);
+        assert_eq!(atomic.load_channel(3, Ordering::Relaxed), 1 << 63);
+
+        atomic.store(RawQuadboard::splat(Nibble::ONE), Ordering::Relaxed);
+        assert_eq!(atomic.into_inner(), RawQuadboard::splat(Nibble::ONE));
+    }
+
+    #[test]
+    fn disjoint_concurrent_writes_do_not_interfere() {
+        let atomic = AtomicRawQuadboard::default();
+
+        std::thread::scope(|scope| {
+            for thread in 0..8u8 {
+                let atomic = &atomic;
+                scope.spawn(move || {
+                    let value = Nibble::try_from(thread + 8).unwrap();
+                    for index in Index::all().filter(|index| index.get() % 8 == thread) {
+                        atomic.set(index, value, Ordering::Relaxed);
+                    }
+                });
+            }
+        });
+
+        let board = atomic.into_inner();
+        for index in Index::all() {
+            assert_eq!(board.get(index).get(), index.get() % 8 + 8);
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Quadboards with an arbitrary number of squares.
+//!
+//! A [`RawBlockQuadboard<N, B>`] holds `N` [`Nibble`] values in `B`
+//! [`RawQuadboard`] blocks of 64 elements each: square `i` is the element
+//! `i % 64` of block `i / 64`. Since the number of blocks can't yet be
+//! computed from `N` on stable Rust, it is given explicitly and must be large
+//! enough that `N <= 64 * B`. The elements past the last square are always
+//! zero, so that equality of boards coincides with equality of their squares.
+//!
+//! The 81-square boards of [`quadboard81`](crate::quadboard81) are built on
+//! [`RawBlockQuadboard<81, 2>`], adding 9×9 indexing and transforms.
+//!
+//! The 64-square case is just [`RawQuadboard`] and [`Quadboard`](crate::Quadboard),
+//! which pay nothing for this generality.
+
+use core::marker::PhantomData;
+
+use halfling::Nibble;
+
+use crate::codec::EmptySquare;
+use crate::raw_quadboard::RawQuadboard;
+
+/// An untyped 10×10 board, as used by international draughts.
+pub type RawQuadboard100 = RawBlockQuadboard<100, 2>;
+
+/// An untyped 16×8 board, as used by the 0x88 layout.
+pub type RawQuadboard128 = RawBlockQuadboard<128, 2>;
+
+/// An untyped quadboard of `N` [`Nibble`] values, held in `B` blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RawBlockQuadboard<const N: usize, const B: usize> {
+    blocks: [RawQuadboard; B],
+}
+
+impl<const N: usize, const B: usize> Default for RawBlockQuadboard<N, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize, const B: usize> RawBlockQuadboard<N, B> {
+    /// Checks that `B` blocks are enough to hold `N` squares.
+    const VALID_SIZE: () = assert!(N <= 64 * B, "too few blocks for the number of squares");
+
+    /// The number of squares on the board.
+    pub const LEN: usize = N;
+
+    /// Creates a new board with every element set to the zero nibble.
+    pub const fn new() -> Self {
+        let () = Self::VALID_SIZE;
+
+        Self {
+            blocks: [RawQuadboard::from_channels([0; 4]); B],
+        }
+    }
+
+    /// Creates a new board with every element set to `value`.
+    pub fn splat(value: Nibble) -> Self {
+        let mut board = Self::new();
+
+        for (k, block) in board.blocks.iter_mut().enumerate() {
+            block.fill_masked(Self::block_mask(k), value);
+        }
+
+        board
+    }
+
+    /// Returns the mask of the elements of block `k` which are squares.
+    const fn block_mask(k: usize) -> u64 {
+        match N.saturating_sub(64 * k) {
+            0 => 0,
+            len @ 1..64 => (1 << len) - 1,
+            _ => u64::MAX,
+        }
+    }
+
+    /// Returns the [`RawQuadboard`] blocks making up `self`.
+    pub const fn as_blocks(&self) -> &[RawQuadboard; B] {
+        &self.blocks
+    }
+
+    /// Returns the [`Nibble`] at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than `N`.
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> Nibble {
+        assert!(index < N, "index {index} is out of bounds for {N} squares");
+        unsafe { self.get_unchecked(index) }
+    }
+
+    /// Writes `value` to the element at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than `N`.
+    #[inline(always)]
+    pub fn set(&mut self, index: usize, value: Nibble) {
+        assert!(index < N, "index {index} is out of bounds for {N} squares");
+        unsafe { self.set_unchecked(index, value) }
+    }
+
+    /// Returns the [`Nibble`] at `index` without bounds checking.
+    ///
+    /// # Safety
+    /// `index` must be strictly less than `N`.
+    #[inline(always)]
+    pub unsafe fn get_unchecked(&self, index: usize) -> Nibble {
+        let block = unsafe { self.blocks.get_unchecked(index / 64) };
+        unsafe { block.get_unchecked((index % 64) as u8) }
+    }
+
+    /// Writes `value` to `index` without bounds checking.
+    ///
+    /// # Safety
+    /// `index` must be strictly less than `N`.
+    #[inline(always)]
+    pub unsafe fn set_unchecked(&mut self, index: usize, value: Nibble) {
+        let block = unsafe { self.blocks.get_unchecked_mut(index / 64) };
+        unsafe { block.set_unchecked((index % 64) as u8, value) }
+    }
+}
+
+/// A typed 10×10 board, as used by international draughts.
+pub type Quadboard100<T> = BlockQuadboard<T, 100, 2>;
+
+/// A typed 16×8 board, as used by the 0x88 layout.
+pub type Quadboard128<T> = BlockQuadboard<T, 128, 2>;
+
+/// A fixed-length buffer of `N` `T` values, held in `B` blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockQuadboard<T, const N: usize, const B: usize> {
+    inner: RawBlockQuadboard<N, B>,
+    _data: PhantomData<T>,
+}
+
+impl<T, const N: usize, const B: usize> Default for BlockQuadboard<T, N, B>
+where
+    T: Default + Into<Nibble>,
+{
+    fn default() -> Self {
+        Self {
+            inner: RawBlockQuadboard::splat(T::default().into()),
+            _data: PhantomData,
+        }
+    }
+}
+
+impl<T, const N: usize, const B: usize> BlockQuadboard<T, N, B> {
+    /// Creates a new board with every element set to the zero nibble.
+    pub const fn new_zeroed() -> Self
+    where
+        T: EmptySquare,
+    {
+        Self {
+            inner: RawBlockQuadboard::new(),
+            _data: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the underlying [`RawBlockQuadboard`].
+    pub const fn as_raw(&self) -> &RawBlockQuadboard<N, B> {
+        &self.inner
+    }
+
+    /// Returns the value at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than `N`.
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> T
+    where
+        Nibble: Into<T>,
+    {
+        self.inner.get(index).into()
+    }
+
+    /// Converts `value` into a [`Nibble`] and writes it to the element at
+    /// `index`.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than `N`.
+    #[inline(always)]
+    pub fn set(&mut self, index: usize, value: T)
+    where
+        T: Into<Nibble>,
+    {
+        self.inner.set(index, value.into());
+    }
+
+    /// Reads the [`Nibble`] at the given index and passes it to `T::from`.
+    ///
+    /// # Safety
+    /// `index` must be strictly less than `N`.
+    #[inline(always)]
+    pub unsafe fn get_unchecked(&self, index: usize) -> T
+    where
+        Nibble: Into<T>,
+    {
+        unsafe { self.inner.get_unchecked(index) }.into()
+    }
+
+    /// Converts `value` into a [`Nibble`] and writes it to `index` without
+    /// bounds checking.
+    ///
+    /// # Safety
+    /// `index` must be strictly less than `N`.
+    #[inline(always)]
+    pub unsafe fn set_unchecked(&mut self, index: usize, value: T)
+    where
+        T: Into<Nibble>,
+    {
+        unsafe { self.inner.set_unchecked(index, value.into()) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splat_leaves_padding_zeroed() {
+        let board = RawQuadboard100::splat(Nibble::NINE);
+
+        assert_eq!(board.as_blocks()[0], RawQuadboard::splat(Nibble::NINE));
+        assert_eq!(board.as_blocks()[1].eq_mask(Nibble::NINE), (1 << 36) - 1);
+        assert_eq!(board.as_blocks()[1].occupancy(), (1 << 36) - 1);
+        assert!((0..100).all(|index| board.get(index) == Nibble::NINE));
+    }
+
+    #[test]
+    fn squares_map_onto_blocks() {
+        let mut board = RawQuadboard128::new();
+        board.set(5, Nibble::ONE);
+        board.set(64, Nibble::TWO);
+        board.set(127, Nibble::THREE);
+
+        assert_eq!(board.as_blocks()[0].eq_mask(Nibble::ONE), 1 << 5);
+        assert_eq!(board.as_blocks()[1].eq_mask(Nibble::TWO), 1);
+        assert_eq!(board.as_blocks()[1].eq_mask(Nibble::THREE), 1 << 63);
+        assert_eq!(board.get(127), Nibble::THREE);
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_panics_past_last_square() {
+        RawQuadboard100::new().get(100);
+    }
+
+    #[test]
+    fn typed_boards_round_trip() {
+        let mut board = Quadboard100::<Nibble>::new_zeroed();
+        board.set(99, Nibble::FIFTEEN);
+
+        assert_eq!(board.get(99), Nibble::FIFTEEN);
+        assert_eq!(unsafe { board.get_unchecked(0) }, Nibble::ZERO);
+        assert_eq!(board.as_raw().as_blocks()[1].occupancy(), 1 << 35);
+    }
+}
@@ -0,0 +1,270 @@
+//! Zero-copy random access to board streams held in memory.
+//!
+//! A [`BoardFile`] borrows boards directly out of any byte storage holding a
+//! board stream (see [`io`](crate::io)). It is generic over the storage so
+//! that it can be backed by a memory map from the crate of your choice, or
+//! simply by a `Vec<u8>`.
+
+use std::fmt;
+
+use halfling::Nibble;
+
+use crate::checksum::ChecksumMismatchError;
+use crate::index::Index;
+use crate::io::StreamFormat;
+use crate::raw_quadboard::RawQuadboard;
+
+/// The error produced when a byte buffer is not a valid board stream.
+#[derive(Debug, PartialEq, Eq)]
+pub enum InvalidBoardFileError {
+    /// The buffer was too short to contain the length prefix.
+    MissingLengthPrefix,
+    /// The buffer did not contain a whole number of boards.
+    TrailingBytes(usize),
+    /// The number of boards disagreed with the length prefix.
+    LengthMismatch {
+        /// The number of boards declared by the length prefix.
+        expected: u64,
+        /// The number of boards actually present.
+        found: usize,
+    },
+}
+
+impl fmt::Display for InvalidBoardFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingLengthPrefix => {
+                write!(f, "The board file is too short to contain a length prefix.")
+            }
+            Self::TrailingBytes(count) => {
+                write!(
+                    f,
+                    "The board file has {count} trailing bytes after its last board."
+                )
+            }
+            Self::LengthMismatch { expected, found } => {
+                write!(
+                    f,
+                    "The board file declares {expected} boards, but contains {found}."
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for InvalidBoardFileError {}
+
+/// Zero-copy random access to a board stream held in memory.
+///
+/// The layout of the stream is validated once on construction, after which
+/// individual boards are borrowed from the storage as [`QuadboardRef`]s
+/// without any copying or decoding.
+#[derive(Debug, Clone)]
+pub struct BoardFile<B> {
+    storage: B,
+    format: StreamFormat,
+    len: usize,
+}
+
+impl<B: AsRef<[u8]>> BoardFile<B> {
+    /// Wraps `storage`, checking that it is a well-formed board stream with
+    /// the given `format`.
+    pub fn new(storage: B, format: StreamFormat) -> Result<Self, InvalidBoardFileError> {
+        let bytes = storage.as_ref();
+
+        let (prefix, body) = match format.length_prefix {
+            true if bytes.len() < 8 => return Err(InvalidBoardFileError::MissingLengthPrefix),
+            true => {
+                let (prefix, body) = bytes.split_at(8);
+                (Some(u64::from_le_bytes(prefix.try_into().unwrap())), body)
+            }
+            false => (None, bytes),
+        };
+
+        let len = body.len() / format.board_len();
+        match body.len() % format.board_len() {
+            0 => (),
+            trailing => return Err(InvalidBoardFileError::TrailingBytes(trailing)),
+        }
+
+        match prefix {
+            Some(expected) if expected != len as u64 => {
+                Err(InvalidBoardFileError::LengthMismatch {
+                    expected,
+                    found: len,
+                })
+            }
+            _ => Ok(Self {
+                storage,
+                format,
+                len,
+            }),
+        }
+    }
+
+    /// Returns the number of boards in the file.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the file contains no boards.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the format of the underlying stream.
+    pub const fn format(&self) -> StreamFormat {
+        self.format
+    }
+
+    /// Returns a reference to the board at position `index` in the file, or
+    /// `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<QuadboardRef<'_>> {
+        if index >= self.len {
+            return None;
+        }
+
+        let offset = match self.format.length_prefix {
+            true => 8,
+            false => 0,
+        };
+
+        let start = offset + index * self.format.board_len();
+        let bytes = &self.storage.as_ref()[start..start + self.format.board_len()];
+
+        Some(QuadboardRef {
+            bytes: bytes[..32].try_into().unwrap(),
+            checksum: match self.format.checksum {
+                true => Some(u32::from_le_bytes(bytes[32..].try_into().unwrap())),
+                false => None,
+            },
+        })
+    }
+
+    /// Returns an iterator over the boards in the file, in order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = QuadboardRef<'_>> + '_ {
+        (0..self.len).map(|index| self.get(index).unwrap())
+    }
+
+    /// Consumes `self` and returns the underlying storage.
+    pub fn into_inner(self) -> B {
+        self.storage
+    }
+}
+
+/// A borrowed view of a canonically-encoded board.
+///
+/// Reads through a [`QuadboardRef`] decode nibbles straight from the
+/// underlying bytes; use [`QuadboardRef::to_raw`] to copy the board out
+/// when many reads are required.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuadboardRef<'a> {
+    bytes: &'a [u8; 32],
+    checksum: Option<u32>,
+}
+
+impl<'a> QuadboardRef<'a> {
+    /// Returns the [`Nibble`] at `index`.
+    pub fn get(&self, index: Index) -> Nibble {
+        let index = index.get() as usize;
+        let (byte, bit) = (index / 8, index % 8);
+
+        let value = (0..4).fold(0u8, |acc, channel| {
+            acc | (((self.bytes[8 * channel + byte] >> bit) & 1) << channel)
+        });
+
+        unsafe { Nibble::new_unchecked(value) }
+    }
+
+    /// Returns the canonical encoding of the board.
+    pub const fn as_bytes(&self) -> &'a [u8; 32] {
+        self.bytes
+    }
+
+    /// Returns the stored checksum of the board, if the stream had one.
+    pub const fn checksum(&self) -> Option<u32> {
+        self.checksum
+    }
+
+    /// Decodes the board into a [`RawQuadboard`].
+    pub const fn to_raw(&self) -> RawQuadboard {
+        RawQuadboard::from_le_bytes(*self.bytes)
+    }
+
+    /// Decodes the board into a [`RawQuadboard`], checking it against the
+    /// stored checksum if there is one.
+    pub fn verify(&self) -> Result<RawQuadboard, ChecksumMismatchError> {
+        let board = self.to_raw();
+
+        match self.checksum {
+            Some(expected) if expected != board.crc32() => Err(ChecksumMismatchError {
+                expected,
+                found: board.crc32(),
+            }),
+            _ => Ok(board),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::BoardWriter;
+
+    fn sample_boards() -> Vec<RawQuadboard> {
+        (0..5u8)
+            .map(|i| {
+                let mut board = RawQuadboard::splat(Nibble::try_from(i).unwrap());
+                board.set((i * 7).try_into().unwrap(), Nibble::MAX);
+                board
+            })
+            .collect()
+    }
+
+    #[test]
+    fn board_file_gives_random_access_to_boards() {
+        let boards = sample_boards();
+        let mut writer = BoardWriter::with_length_prefix(Vec::new(), true, 5).unwrap();
+        for board in &boards {
+            writer.write_board(board).unwrap();
+        }
+
+        let format = writer.format();
+        let file = BoardFile::new(writer.finish().unwrap(), format).unwrap();
+        assert_eq!(file.len(), 5);
+
+        let third = file.get(3).unwrap();
+        assert_eq!(third.verify().unwrap(), boards[3]);
+        for index in 0..64u8 {
+            let index = index.try_into().unwrap();
+            assert_eq!(third.get(index), boards[3].get(index));
+        }
+
+        assert!(file.get(5).is_none());
+        assert!(file.iter().map(|board| board.to_raw()).eq(boards));
+    }
+
+    #[test]
+    fn board_file_rejects_malformed_storage() {
+        let format = StreamFormat {
+            length_prefix: true,
+            checksum: false,
+        };
+
+        let mut bytes = 2u64.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&[0; 32]);
+        assert_eq!(
+            BoardFile::new(bytes.as_slice(), format).unwrap_err(),
+            InvalidBoardFileError::LengthMismatch {
+                expected: 2,
+                found: 1
+            }
+        );
+
+        bytes.push(0);
+        assert_eq!(
+            BoardFile::new(bytes.as_slice(), format).unwrap_err(),
+            InvalidBoardFileError::TrailingBytes(1)
+        );
+    }
+}
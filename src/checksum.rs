@@ -0,0 +1,154 @@
+//! Integrity checks for persisted quadboards.
+//!
+//! Boards are checksummed with the standard CRC-32 (as used by zlib, PNG,
+//! and friends) over their canonical 32-byte encoding, as given by
+//! [`RawQuadboard::to_le_bytes`].
+
+use crate::raw_quadboard::RawQuadboard;
+
+/// The reflected form of the CRC-32 polynomial `0x04C11DB7`.
+const CRC32_POLYNOMIAL: u32 = 0xEDB88320;
+
+/// A lookup table mapping each byte to its CRC-32 remainder.
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut remainder = i as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            remainder = match remainder & 1 {
+                0 => remainder >> 1,
+                _ => (remainder >> 1) ^ CRC32_POLYNOMIAL,
+            };
+            bit += 1;
+        }
+
+        table[i] = remainder;
+        i += 1;
+    }
+
+    table
+};
+
+/// Computes the CRC-32 checksum of `bytes`.
+pub const fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let lookup = ((crc ^ bytes[i] as u32) & 0xFF) as usize;
+        crc = (crc >> 8) ^ CRC32_TABLE[lookup];
+        i += 1;
+    }
+
+    !crc
+}
+
+/// The error produced when a [`VerifiedBoard`] fails its integrity check.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ChecksumMismatchError {
+    /// The checksum that was stored alongside the board.
+    pub expected: u32,
+    /// The checksum of the board as it was actually read.
+    pub found: u32,
+}
+
+impl core::fmt::Display for ChecksumMismatchError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Checksum mismatch: expected {:#010x}, but the board hashes to {:#010x}.",
+            self.expected, self.found
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ChecksumMismatchError {}
+
+/// A [`RawQuadboard`] paired with its CRC-32 checksum.
+///
+/// This is the intended read path for boards coming from untrusted storage:
+/// a [`VerifiedBoard`] can only be decoded from bytes if the stored checksum
+/// matches the board, and so holding one is proof that the check was done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerifiedBoard {
+    board: RawQuadboard,
+    checksum: u32,
+}
+
+impl VerifiedBoard {
+    /// The length in bytes of an encoded [`VerifiedBoard`], i.e. the 32-byte
+    /// board followed by its 4-byte little-endian checksum.
+    pub const ENCODED_LEN: usize = 36;
+
+    /// Computes the checksum of `board` and pairs them together.
+    pub fn new(board: RawQuadboard) -> Self {
+        Self {
+            board,
+            checksum: board.crc32(),
+        }
+    }
+
+    /// Returns the underlying [`RawQuadboard`].
+    #[inline(always)]
+    pub const fn board(&self) -> RawQuadboard {
+        self.board
+    }
+
+    /// Returns the checksum of the underlying board.
+    #[inline(always)]
+    pub const fn checksum(&self) -> u32 {
+        self.checksum
+    }
+
+    /// Encodes `self` as the board bytes followed by the checksum.
+    pub fn to_bytes(&self) -> [u8; Self::ENCODED_LEN] {
+        let mut bytes = [0u8; Self::ENCODED_LEN];
+        bytes[..32].copy_from_slice(&self.board.to_le_bytes());
+        bytes[32..].copy_from_slice(&self.checksum.to_le_bytes());
+        bytes
+    }
+
+    /// Decodes a [`VerifiedBoard`] from `bytes`, returning an error if the
+    /// stored checksum does not match the decoded board.
+    pub fn from_bytes(bytes: &[u8; Self::ENCODED_LEN]) -> Result<Self, ChecksumMismatchError> {
+        let board = RawQuadboard::from_le_bytes(bytes[..32].try_into().unwrap());
+        let expected = u32::from_le_bytes(bytes[32..].try_into().unwrap());
+        let found = board.crc32();
+
+        match expected == found {
+            true => Ok(Self {
+                board,
+                checksum: found,
+            }),
+            false => Err(ChecksumMismatchError { expected, found }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halfling::Nibble;
+
+    #[test]
+    fn crc32_matches_reference_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF43926);
+    }
+
+    #[test]
+    fn verified_board_detects_corruption() {
+        let mut board = RawQuadboard::default();
+        board.set(12.try_into().unwrap(), Nibble::NINE);
+
+        let mut bytes = VerifiedBoard::new(board).to_bytes();
+        assert_eq!(VerifiedBoard::from_bytes(&bytes).unwrap().board(), board);
+
+        bytes[3] ^= 0b100;
+        assert!(VerifiedBoard::from_bytes(&bytes).is_err());
+    }
+}
@@ -0,0 +1,377 @@
+//! Support for chess, with a standard piece encoding and FEN placement strings.
+//!
+//! The kind of a piece is held in the lower three bits of a nibble (from 1 for
+//! a pawn to 6 for a king) and its color in the top bit, so that the zero
+//! nibble is an empty square and `Quadboard<Square>` is the usual chessboard.
+
+use core::fmt;
+
+use halfling::Nibble;
+
+use crate::codec::EmptySquare;
+use crate::index::{File, Index, Rank};
+use crate::Quadboard;
+
+/// The FEN piece placement of the standard starting position.
+pub const START_PLACEMENT: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR";
+
+/// The side to which a piece belongs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// The side which starts on ranks 1 and 2.
+    White,
+    /// The side which starts on ranks 7 and 8.
+    Black,
+}
+
+/// The kind of a chess piece, irrespective of its color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum Piece {
+    /// A pawn.
+    Pawn = 1,
+    /// A knight.
+    Knight = 2,
+    /// A bishop.
+    Bishop = 3,
+    /// A rook.
+    Rook = 4,
+    /// A queen.
+    Queen = 5,
+    /// A king.
+    King = 6,
+}
+
+/// A chess piece of a particular color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ColoredPiece {
+    /// The color of the piece.
+    pub color: Color,
+    /// The kind of the piece.
+    pub piece: Piece,
+}
+
+/// The contents of a single square of a chessboard.
+///
+/// This is a newtype over `Option<ColoredPiece>`, which exists only because
+/// the orphan rules prevent implementing [`Nibble`] conversions on the
+/// [`Option`] directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Square(pub Option<ColoredPiece>);
+
+/// The error produced when a [`Nibble`] does not encode a [`Square`].
+#[derive(Debug)]
+pub struct InvalidSquareError(pub Nibble);
+
+impl fmt::Display for InvalidSquareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "The nibble {} does not encode a chess square.", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidSquareError {}
+
+/// The error produced when parsing a FEN piece placement fails.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParsePlacementError {
+    /// The placement contained a character which is neither a piece, a
+    /// digit from 1 to 8, nor a rank separator.
+    InvalidCharacter(char),
+    /// The given rank did not describe exactly eight squares.
+    BadRankLength(Rank),
+    /// The placement did not contain exactly eight ranks.
+    BadRankCount,
+}
+
+impl fmt::Display for ParsePlacementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCharacter(c) => {
+                write!(f, "The character {c:?} is not valid in a FEN placement.")
+            }
+            Self::BadRankLength(rank) => {
+                write!(
+                    f,
+                    "Rank {rank} of the FEN placement does not have eight squares."
+                )
+            }
+            Self::BadRankCount => write!(f, "The FEN placement does not have eight ranks."),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParsePlacementError {}
+
+impl ColoredPiece {
+    /// Creates a new [`ColoredPiece`].
+    pub const fn new(color: Color, piece: Piece) -> Self {
+        Self { color, piece }
+    }
+
+    /// Returns the FEN letter of this piece, which is uppercase for white
+    /// and lowercase for black.
+    pub const fn to_char(self) -> char {
+        let c = match self.piece {
+            Piece::Pawn => 'P',
+            Piece::Knight => 'N',
+            Piece::Bishop => 'B',
+            Piece::Rook => 'R',
+            Piece::Queen => 'Q',
+            Piece::King => 'K',
+        };
+
+        match self.color {
+            Color::White => c,
+            Color::Black => c.to_ascii_lowercase(),
+        }
+    }
+
+    /// Returns the piece with the given FEN letter, if any.
+    pub const fn from_char(c: char) -> Option<Self> {
+        let color = match c.is_ascii_uppercase() {
+            true => Color::White,
+            false => Color::Black,
+        };
+
+        let piece = match c.to_ascii_uppercase() {
+            'P' => Piece::Pawn,
+            'N' => Piece::Knight,
+            'B' => Piece::Bishop,
+            'R' => Piece::Rook,
+            'Q' => Piece::Queen,
+            'K' => Piece::King,
+            _ => return None,
+        };
+
+        Some(Self { color, piece })
+    }
+}
+
+impl From<ColoredPiece> for Nibble {
+    fn from(value: ColoredPiece) -> Self {
+        let color = match value.color {
+            Color::White => 0,
+            Color::Black => 0b1000,
+        };
+
+        unsafe { Nibble::new_unchecked(color | value.piece as u8) }
+    }
+}
+
+impl From<Square> for Nibble {
+    fn from(value: Square) -> Self {
+        match value.0 {
+            Some(piece) => piece.into(),
+            None => Nibble::ZERO,
+        }
+    }
+}
+
+impl TryFrom<Nibble> for Square {
+    type Error = InvalidSquareError;
+
+    fn try_from(value: Nibble) -> Result<Self, Self::Error> {
+        let color = match value.get() & 0b1000 {
+            0 => Color::White,
+            _ => Color::Black,
+        };
+
+        let piece = match value.get() & 0b0111 {
+            0 if value.get() == 0 => return Ok(Square(None)),
+            1 => Piece::Pawn,
+            2 => Piece::Knight,
+            3 => Piece::Bishop,
+            4 => Piece::Rook,
+            5 => Piece::Queen,
+            6 => Piece::King,
+            _ => return Err(InvalidSquareError(value)),
+        };
+
+        Ok(Square(Some(ColoredPiece { color, piece })))
+    }
+}
+
+impl From<ColoredPiece> for Square {
+    fn from(value: ColoredPiece) -> Self {
+        Square(Some(value))
+    }
+}
+
+impl From<Option<ColoredPiece>> for Square {
+    fn from(value: Option<ColoredPiece>) -> Self {
+        Square(value)
+    }
+}
+
+impl From<Square> for Option<ColoredPiece> {
+    fn from(value: Square) -> Self {
+        value.0
+    }
+}
+
+impl EmptySquare for Square {}
+
+/// A [`Display`](fmt::Display) adaptor writing the FEN piece placement of a
+/// board, returned by [`placement`].
+#[derive(Debug, Clone, Copy)]
+pub struct Placement<'a>(&'a Quadboard<Square>);
+
+impl fmt::Display for Placement<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for rank in Rank::ALL.iter().rev() {
+            let mut empty = 0;
+
+            for file in File::ALL {
+                match self.0.try_get(Index::from_coords(file, *rank)) {
+                    Ok(Square(Some(piece))) => {
+                        if empty > 0 {
+                            write!(f, "{empty}")?;
+                            empty = 0;
+                        }
+
+                        write!(f, "{}", piece.to_char())?;
+                    }
+                    _ => empty += 1,
+                }
+            }
+
+            if empty > 0 {
+                write!(f, "{empty}")?;
+            }
+
+            if *rank != Rank::First {
+                write!(f, "/")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns an adaptor which displays the FEN piece placement of `board`.
+pub fn placement(board: &Quadboard<Square>) -> Placement<'_> {
+    Placement(board)
+}
+
+/// Parses a FEN piece placement, i.e. the first field of a FEN string,
+/// such as [`START_PLACEMENT`].
+pub fn parse_placement(s: &str) -> Result<Quadboard<Square>, ParsePlacementError> {
+    let mut board = Quadboard::new_zeroed();
+    let mut ranks = s.split('/');
+
+    for rank in Rank::ALL.iter().rev() {
+        let row = ranks.next().ok_or(ParsePlacementError::BadRankCount)?;
+        let mut file = 0u8;
+
+        for c in row.chars() {
+            match (c.to_digit(10), ColoredPiece::from_char(c)) {
+                (Some(skip @ 1..=8), _) => file += skip as u8,
+                (None, Some(piece)) => {
+                    if file < 8 {
+                        let index = Index::from_coords(File::ALL[file as usize], *rank);
+                        board.set(index, Square::from(piece));
+                    }
+
+                    file += 1;
+                }
+                _ => return Err(ParsePlacementError::InvalidCharacter(c)),
+            }
+
+            if file > 8 {
+                return Err(ParsePlacementError::BadRankLength(*rank));
+            }
+        }
+
+        if file != 8 {
+            return Err(ParsePlacementError::BadRankLength(*rank));
+        }
+    }
+
+    match ranks.next() {
+        Some(_) => Err(ParsePlacementError::BadRankCount),
+        None => Ok(board),
+    }
+}
+
+/// Returns the standard starting position.
+pub fn start_position() -> Quadboard<Square> {
+    parse_placement(START_PLACEMENT).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn square_encoding_round_trips() {
+        let mut valid = 0;
+
+        for value in 0..16u8 {
+            let nibble = Nibble::try_from(value).unwrap();
+
+            if let Ok(square) = Square::try_from(nibble) {
+                assert_eq!(Nibble::from(square), nibble);
+                valid += 1;
+            }
+        }
+
+        // the empty square and six pieces of each color
+        assert_eq!(valid, 13);
+    }
+
+    #[test]
+    fn fen_letters_round_trip() {
+        for c in "PNBRQKpnbrqk".chars() {
+            assert_eq!(
+                ColoredPiece::from_char(c).map(ColoredPiece::to_char),
+                Some(c)
+            );
+        }
+
+        assert_eq!(ColoredPiece::from_char('x'), None);
+    }
+
+    #[test]
+    fn start_position_round_trips_through_fen() {
+        let board = start_position();
+
+        assert_eq!(placement(&board).to_string(), START_PLACEMENT);
+        assert_eq!(
+            board.try_get("e1".parse().unwrap()).unwrap(),
+            Square::from(ColoredPiece::new(Color::White, Piece::King))
+        );
+        assert_eq!(
+            board.try_get("d8".parse().unwrap()).unwrap(),
+            Square::from(ColoredPiece::new(Color::Black, Piece::Queen))
+        );
+        assert_eq!(board.as_raw_quadboard().occupancy(), 0xFFFF_0000_0000_FFFF);
+    }
+
+    #[test]
+    fn placement_with_gaps_round_trips() {
+        let fen = "r3k2r/8/8/3pP3/8/8/8/R3K2R";
+        assert_eq!(placement(&parse_placement(fen).unwrap()).to_string(), fen);
+    }
+
+    #[test]
+    fn invalid_placements_are_rejected() {
+        assert_eq!(
+            parse_placement("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP"),
+            Err(ParsePlacementError::BadRankCount)
+        );
+        assert_eq!(
+            parse_placement("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR/8"),
+            Err(ParsePlacementError::BadRankCount)
+        );
+        assert_eq!(
+            parse_placement("rnbqkbnr/ppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR"),
+            Err(ParsePlacementError::BadRankLength(Rank::Seventh))
+        );
+        assert_eq!(
+            parse_placement("rnbqkbnr/pppppppp/9/8/8/8/PPPPPPPP/RNBQKBNR"),
+            Err(ParsePlacementError::InvalidCharacter('9'))
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! Traits describing the nibble encodings of typed values.
+
+use halfling::Nibble;
+
+/// A marker trait for types whose encoding maps the zero nibble to a valid
+/// value, conventionally representing an empty square.
+///
+/// Implementing this trait is a promise that decoding [`Nibble::ZERO`] (via
+/// `From<Nibble>` or `TryFrom<Nibble>`) always succeeds. It is what allows
+/// zero-initialised boards to be constructed for types that intentionally
+/// don't implement [`Default`].
+pub trait EmptySquare {}
+
+/// Every nibble, including [`Nibble::ZERO`], is trivially a valid [`Nibble`].
+impl EmptySquare for Nibble {}
+
+/// A trait for types with a fixed nibble encoding, recording which of the
+/// sixteen nibbles encode a valid value.
+///
+/// With the `derive` feature enabled, this can be derived for fieldless
+/// enums along with the [`Nibble`] conversions, as in the following.
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use quadboard::codec::NibbleEncoding;
+/// use quadboard::Nibble;
+///
+/// #[derive(Debug, PartialEq, NibbleEncoding)]
+/// enum Cell {
+///     Empty,
+///     Wall = 4,
+///     Goal,
+/// }
+///
+/// assert_eq!(Cell::VALID, 0b110001);
+/// assert_eq!(Cell::try_from(Nibble::FIVE), Ok(Cell::Goal));
+/// assert!(Cell::try_from(Nibble::ONE).is_err());
+/// # }
+/// ```
+pub trait NibbleEncoding: TryFrom<Nibble> + Into<Nibble> {
+    /// A mask with bit `n` set if and only if the nibble `n` encodes a value.
+    const VALID: u16;
+
+    /// Returns `true` if `nibble` encodes a value.
+    #[inline(always)]
+    fn is_valid(nibble: Nibble) -> bool {
+        Self::VALID & (1 << nibble.get()) != 0
+    }
+}
+
+#[cfg(feature = "derive")]
+pub use quadboard_derive::NibbleEncoding;
+
+/// The error produced when a [`Nibble`] does not encode a value of a type
+/// with a derived [`NibbleEncoding`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvalidNibbleError(pub Nibble);
+
+impl core::fmt::Display for InvalidNibbleError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "The nibble {} does not encode a value of this type.",
+            self.0
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidNibbleError {}
+
+/// Returns the result of decoding each of the sixteen nibbles as a `T`,
+/// indexed by the value of the nibble.
+///
+/// This is mostly useful for debugging and for validating an encoding at
+/// startup, as in the following.
+///
+/// ```
+/// use std::num::NonZeroU8;
+/// use quadboard::codec::encoding_table;
+///
+/// let table = encoding_table::<NonZeroU8>();
+/// assert!(table[0].is_err());
+/// assert!(table[1..].iter().all(Result::is_ok));
+/// ```
+pub fn encoding_table<T: TryFrom<Nibble>>() -> [Result<T, T::Error>; 16] {
+    core::array::from_fn(|value| {
+        let nibble = unsafe { Nibble::new_unchecked(value as u8) };
+        T::try_from(nibble)
+    })
+}
+
+#[cfg(all(test, feature = "derive"))]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, NibbleEncoding)]
+    enum Piece {
+        Empty,
+        Pawn,
+        King = 6,
+        Marker = 15,
+    }
+
+    #[test]
+    fn derived_encoding_round_trips() {
+        assert_eq!(Piece::VALID, 0b1000_0000_0100_0011);
+
+        for value in encoding_table::<Piece>().into_iter().flatten() {
+            assert_eq!(Piece::try_from(Nibble::from(value)), Ok(value));
+        }
+
+        assert_eq!(Nibble::from(Piece::King), Nibble::SIX);
+        assert_eq!(
+            Piece::try_from(Nibble::SEVEN),
+            Err(InvalidNibbleError(Nibble::SEVEN))
+        );
+        assert!(Piece::is_valid(Nibble::FIFTEEN));
+        assert!(!Piece::is_valid(Nibble::TWO));
+    }
+}
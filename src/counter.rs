@@ -0,0 +1,229 @@
+//! Quadboards as arrays of 4-bit counters.
+//!
+//! Rather than a categorical value, each element of a [`CounterBoard`] is an
+//! unsigned counter in the range `0..=15`; this is the natural representation
+//! for attack counts, mobility maps, and similar per-square tallies. All the
+//! arithmetic is bit-sliced across the four channels, so each operation acts
+//! on every square at once with a handful of word-level instructions.
+
+use halfling::Nibble;
+
+use crate::index::Index;
+use crate::raw_quadboard::RawQuadboard;
+
+/// A quadboard of 64 saturating 4-bit counters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CounterBoard {
+    inner: RawQuadboard,
+}
+
+impl From<RawQuadboard> for CounterBoard {
+    fn from(value: RawQuadboard) -> Self {
+        Self { inner: value }
+    }
+}
+
+impl From<CounterBoard> for RawQuadboard {
+    fn from(value: CounterBoard) -> Self {
+        value.inner
+    }
+}
+
+impl CounterBoard {
+    /// Creates a new [`CounterBoard`] with every counter set to zero.
+    pub const fn new() -> Self {
+        Self::from_channels([0; 4])
+    }
+
+    /// Creates a new [`CounterBoard`] with every counter set to `value`.
+    pub fn splat(value: Nibble) -> Self {
+        Self {
+            inner: RawQuadboard::splat(value),
+        }
+    }
+
+    /// Creates a new [`CounterBoard`] with the counters in `mask` set to 1,
+    /// and every other counter set to 0.
+    pub const fn from_mask(mask: u64) -> Self {
+        Self::from_channels([mask, 0, 0, 0])
+    }
+
+    /// Constructs a [`CounterBoard`] from bit-sliced channels.
+    const fn from_channels(channels: [u64; 4]) -> Self {
+        Self {
+            inner: RawQuadboard::from_channels(channels),
+        }
+    }
+
+    /// Returns the bit-sliced channels of `self`.
+    const fn channels(&self) -> [u64; 4] {
+        *self.inner.as_channels()
+    }
+
+    /// Returns a reference to the underlying [`RawQuadboard`].
+    pub const fn as_raw_quadboard(&self) -> &RawQuadboard {
+        &self.inner
+    }
+
+    /// Returns the value of the counter at `index`.
+    #[inline(always)]
+    pub fn get(&self, index: Index) -> u8 {
+        self.inner.get(index).get()
+    }
+
+    /// Sets the counter at `index` to `value`.
+    #[inline(always)]
+    pub fn set(&mut self, index: Index, value: Nibble) {
+        self.inner.set(index, value)
+    }
+
+    /// Adds `other` to `self` elementwise, saturating at 15.
+    ///
+    /// See [`RawQuadboard::saturating_add`] for details.
+    pub const fn saturating_add(&self, other: &Self) -> Self {
+        Self {
+            inner: self.inner.saturating_add(&other.inner),
+        }
+    }
+
+    /// Subtracts `other` from `self` elementwise, saturating at 0.
+    ///
+    /// See [`RawQuadboard::saturating_sub`] for details.
+    pub const fn saturating_sub(&self, other: &Self) -> Self {
+        Self {
+            inner: self.inner.saturating_sub(&other.inner),
+        }
+    }
+
+    /// Increments every counter in `mask` by one, saturating at 15.
+    pub fn increment(&mut self, mask: u64) {
+        *self = self.saturating_add(&Self::from_mask(mask));
+    }
+
+    /// Decrements every counter in `mask` by one, saturating at 0.
+    pub fn decrement(&mut self, mask: u64) {
+        *self = self.saturating_sub(&Self::from_mask(mask));
+    }
+
+    /// Adds `other` to the counters of `self` in `mask`, saturating at 15.
+    pub fn add_masked(&mut self, other: &Self, mask: u64) {
+        let sum = self.saturating_add(other);
+        self.inner = self.inner.blend(mask, &sum.inner);
+    }
+
+    /// Resets every counter in `mask` to zero.
+    pub fn clear_masked(&mut self, mask: u64) {
+        self.inner.fill_masked(mask, Nibble::ZERO);
+    }
+
+    /// Returns a bitmask of the counters in `self` strictly greater than
+    /// the corresponding counters in `other`.
+    pub const fn gt_mask(&self, other: &Self) -> u64 {
+        let (a, b) = (self.channels(), other.channels());
+        let mut greater = 0u64;
+        let mut equal = u64::MAX;
+        let mut k = 4;
+
+        // compare from the most significant bit down
+        while k > 0 {
+            k -= 1;
+            greater |= equal & a[k] & !b[k];
+            equal &= !(a[k] ^ b[k]);
+        }
+
+        greater
+    }
+
+    /// Returns the elementwise maximum of `self` and `other`.
+    pub fn max(&self, other: &Self) -> Self {
+        Self {
+            inner: other.inner.blend(self.gt_mask(other), &self.inner),
+        }
+    }
+
+    /// Returns the elementwise minimum of `self` and `other`.
+    pub fn min(&self, other: &Self) -> Self {
+        Self {
+            inner: self.inner.blend(self.gt_mask(other), &other.inner),
+        }
+    }
+
+    /// Halves every counter, rounding down.
+    pub const fn decay(&self) -> Self {
+        let [_, c1, c2, c3] = self.channels();
+        Self::from_channels([c1, c2, c3, 0])
+    }
+
+    /// Returns a bitmask of the counters which are at least `threshold`.
+    pub fn at_least(&self, threshold: Nibble) -> u64 {
+        !Self::splat(threshold).gt_mask(self)
+    }
+
+    /// Returns a bitmask of the nonzero counters.
+    pub const fn nonzero_mask(&self) -> u64 {
+        let [c0, c1, c2, c3] = self.channels();
+        c0 | c1 | c2 | c3
+    }
+
+    /// Returns the sum of all the counters.
+    pub const fn total(&self) -> u32 {
+        let [c0, c1, c2, c3] = self.channels();
+        c0.count_ones() + (c1.count_ones() << 1) + (c2.count_ones() << 2) + (c3.count_ones() << 3)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a board whose counter at each index `i` is `f(i)`.
+    fn counters(f: impl Fn(u8) -> u8) -> CounterBoard {
+        let mut board = CounterBoard::new();
+        for i in 0..64u8 {
+            board.set(i.try_into().unwrap(), Nibble::try_from(f(i)).unwrap());
+        }
+        board
+    }
+
+    #[test]
+    fn counter_arithmetic_matches_scalar_arithmetic() {
+        let a = counters(|i| i % 16);
+        let b = counters(|i| (i / 4) % 16);
+
+        let sum = a.saturating_add(&b);
+        let difference = a.saturating_sub(&b);
+        let max = a.max(&b);
+        let min = a.min(&b);
+        let decayed = a.decay();
+
+        for i in 0..64u8 {
+            let index = i.try_into().unwrap();
+            let (x, y) = (a.get(index), b.get(index));
+
+            assert_eq!(sum.get(index), (x + y).min(15));
+            assert_eq!(difference.get(index), x.saturating_sub(y));
+            assert_eq!(max.get(index), x.max(y));
+            assert_eq!(min.get(index), x.min(y));
+            assert_eq!(decayed.get(index), x / 2);
+        }
+
+        assert_eq!(a.total(), (0..64u32).map(|i| i % 16).sum());
+        assert_eq!(a.at_least(Nibble::FOURTEEN).count_ones(), 8);
+    }
+
+    #[test]
+    fn masked_counter_operations_only_touch_the_mask() {
+        let mut board = CounterBoard::splat(Nibble::FOURTEEN);
+        board.increment(0b11);
+        board.increment(0b01);
+        board.decrement(0b100);
+
+        assert_eq!(board.get(0.try_into().unwrap()), 15);
+        assert_eq!(board.get(1.try_into().unwrap()), 15);
+        assert_eq!(board.get(2.try_into().unwrap()), 13);
+        assert_eq!(board.get(3.try_into().unwrap()), 14);
+
+        board.clear_masked(!0b1);
+        assert_eq!(board.nonzero_mask(), 0b1);
+    }
+}
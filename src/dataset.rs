@@ -0,0 +1,312 @@
+//! Utilities for working with large collections of boards.
+//!
+//! # Randomness
+//! Rather than depending on a particular random number generator, the
+//! sampling functions in this module draw from any `FnMut() -> u64` that
+//! produces uniformly distributed values, so for example a `rand` generator
+//! can be passed as `&mut || rng.next_u64()`.
+
+use std::collections::BTreeMap;
+
+use halfling::Nibble;
+
+use crate::index::Index;
+use crate::raw_quadboard::RawQuadboard;
+
+/// Aggregate value frequencies over a collection of boards.
+///
+/// For every square and every nibble value, this records how many of the
+/// boards hold that value at that square; the per-value totals across all
+/// squares are derived from this table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardStatistics {
+    square_counts: [[u64; 16]; 64],
+    boards: u64,
+}
+
+impl Default for BoardStatistics {
+    fn default() -> Self {
+        Self {
+            square_counts: [[0; 16]; 64],
+            boards: 0,
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a RawQuadboard> for BoardStatistics {
+    fn from_iter<I: IntoIterator<Item = &'a RawQuadboard>>(iter: I) -> Self {
+        let mut statistics = Self::default();
+        let mut tally = Tally::default();
+
+        for board in iter {
+            tally.add(board);
+
+            if tally.is_full() {
+                statistics.flush(&mut tally);
+            }
+        }
+
+        statistics.flush(&mut tally);
+        statistics
+    }
+}
+
+impl BoardStatistics {
+    /// Computes the statistics of the given boards.
+    pub fn from_boards(boards: &[RawQuadboard]) -> Self {
+        boards.iter().collect()
+    }
+
+    /// Records the values of `board`.
+    pub fn add(&mut self, board: &RawQuadboard) {
+        let mut tally = Tally::default();
+        tally.add(board);
+        self.flush(&mut tally);
+    }
+
+    /// Adds the counts held by `tally` to `self`, and resets it.
+    fn flush(&mut self, tally: &mut Tally) {
+        for (value, planes) in tally.planes.iter().enumerate() {
+            for (k, &plane) in planes.iter().enumerate() {
+                let mut squares = plane;
+
+                while squares != 0 {
+                    let square = squares.trailing_zeros() as usize;
+                    self.square_counts[square][value] += 1 << k;
+                    squares &= squares - 1;
+                }
+            }
+        }
+
+        self.boards += tally.boards as u64;
+        *tally = Tally::default();
+    }
+
+    /// Returns the number of boards that have been recorded.
+    pub const fn boards(&self) -> u64 {
+        self.boards
+    }
+
+    /// Returns the full table of frequencies, indexed first by square and
+    /// then by nibble value.
+    pub const fn square_counts(&self) -> &[[u64; 16]; 64] {
+        &self.square_counts
+    }
+
+    /// Returns the number of recorded boards holding `value` at `index`.
+    pub const fn count(&self, index: Index, value: Nibble) -> u64 {
+        self.square_counts[index.get() as usize][value.get() as usize]
+    }
+
+    /// Returns the total number of squares holding each nibble value,
+    /// summed over all the recorded boards.
+    pub fn value_totals(&self) -> [u64; 16] {
+        let mut totals = [0u64; 16];
+
+        for counts in &self.square_counts {
+            for (total, count) in totals.iter_mut().zip(counts) {
+                *total += count;
+            }
+        }
+
+        totals
+    }
+}
+
+/// Bit-sliced per-square counters for up to 255 boards at a time.
+///
+/// Plane `k` of value `n` holds bit `k` of the number of boards with the
+/// nibble `n` at each square, so a board is counted with a handful of
+/// bitwise operations per value rather than by decoding its squares.
+#[derive(Debug, Default)]
+struct Tally {
+    planes: [[u64; 8]; 16],
+    boards: u8,
+}
+
+impl Tally {
+    /// Returns `true` if counting another board could overflow the planes.
+    const fn is_full(&self) -> bool {
+        self.boards == u8::MAX
+    }
+
+    /// Counts the values of `board`.
+    fn add(&mut self, board: &RawQuadboard) {
+        debug_assert!(!self.is_full());
+        let [c0, c1, c2, c3] = board.into_channels();
+
+        // the masks of each combination of the lower and upper pairs of bits,
+        // as in `RawQuadboard::counts`
+        let low = [!c1 & !c0, !c1 & c0, c1 & !c0, c1 & c0];
+        let high = [!c3 & !c2, !c3 & c2, c3 & !c2, c3 & c2];
+
+        for (value, planes) in self.planes.iter_mut().enumerate() {
+            // a ripple-carry increment of the squares holding `value`
+            let mut carry = high[value >> 2] & low[value & 0b11];
+
+            for plane in planes.iter_mut() {
+                if carry == 0 {
+                    break;
+                }
+
+                (*plane, carry) = (*plane ^ carry, *plane & carry);
+            }
+        }
+
+        self.boards += 1;
+    }
+}
+
+/// Returns a uniformly distributed value in `0..bound` drawn from `rng`.
+///
+/// This uses Lemire's multiply-and-reject method, so it is free of modulo bias.
+fn uniform_below(bound: u64, rng: &mut impl FnMut() -> u64) -> u64 {
+    debug_assert!(bound > 0);
+    let threshold = bound.wrapping_neg() % bound;
+
+    loop {
+        let product = (rng() as u128) * (bound as u128);
+        if (product as u64) >= threshold {
+            return (product >> 64) as u64;
+        }
+    }
+}
+
+/// Shuffles `boards` in place, such that every permutation is equally likely.
+pub fn shuffle<B>(boards: &mut [B], rng: &mut impl FnMut() -> u64) {
+    for i in (1..boards.len()).rev() {
+        let j = uniform_below(i as u64 + 1, rng) as usize;
+        boards.swap(i, j);
+    }
+}
+
+/// Selects `n` distinct boards uniformly at random, preserving their
+/// relative order. If `n` is at least `boards.len()`, every board is returned.
+pub fn sample<'a, B>(boards: &'a [B], n: usize, rng: &mut impl FnMut() -> u64) -> Vec<&'a B> {
+    let mut needed = n.min(boards.len());
+    let mut selected = Vec::with_capacity(needed);
+
+    // selection sampling (Knuth's Algorithm S): each board is taken with
+    // probability equal to the number still needed over the number left
+    for (i, board) in boards.iter().enumerate() {
+        if needed == 0 {
+            break;
+        }
+
+        let left = (boards.len() - i) as u64;
+        if uniform_below(left, rng) < needed as u64 {
+            selected.push(board);
+            needed -= 1;
+        }
+    }
+
+    selected
+}
+
+/// Groups `boards` into strata by `key` and selects up to `per_stratum`
+/// boards uniformly at random from each, as in [`sample`].
+///
+/// The result is returned as a map from each key to its selected boards.
+pub fn stratified_sample<'a, B, K: Ord>(
+    boards: &'a [B],
+    per_stratum: usize,
+    mut key: impl FnMut(&B) -> K,
+    rng: &mut impl FnMut() -> u64,
+) -> BTreeMap<K, Vec<&'a B>> {
+    let mut strata: BTreeMap<K, Vec<&'a B>> = BTreeMap::new();
+    for board in boards {
+        strata.entry(key(board)).or_default().push(board);
+    }
+
+    for stratum in strata.values_mut() {
+        let selected = sample(stratum, per_stratum, rng);
+        *stratum = selected.into_iter().copied().collect();
+    }
+
+    strata
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn board_statistics_counts_every_square() {
+        let mut board = RawQuadboard::default();
+        board.set(9.try_into().unwrap(), Nibble::SEVEN);
+
+        let boards = [board, board, RawQuadboard::splat(Nibble::SEVEN)];
+        let statistics = BoardStatistics::from_boards(&boards);
+
+        assert_eq!(statistics.boards(), 3);
+        assert_eq!(statistics.count(9.try_into().unwrap(), Nibble::SEVEN), 3);
+        assert_eq!(statistics.count(0.try_into().unwrap(), Nibble::ZERO), 2);
+
+        let totals = statistics.value_totals();
+        assert_eq!(totals[7], 66);
+        assert_eq!(totals[0], 126);
+        assert_eq!(totals.iter().sum::<u64>(), 3 * 64);
+    }
+
+    #[test]
+    fn board_statistics_agree_across_tally_flushes() {
+        let boards: Vec<RawQuadboard> = (0..600u64)
+            .map(|n| RawQuadboard::from_channels([n, n * 3, n.rotate_left(7), !n]))
+            .collect();
+        let statistics = BoardStatistics::from_boards(&boards);
+
+        let mut incremental = BoardStatistics::default();
+        boards.iter().for_each(|board| incremental.add(board));
+        assert_eq!(statistics, incremental);
+
+        for index in [0u8, 7, 63].map(|i| Index::try_from(i).unwrap()) {
+            for value in (0..16).map(|v| Nibble::try_from(v).unwrap()) {
+                let expected = boards.iter().filter(|board| board.get(index) == value);
+                assert_eq!(statistics.count(index, value), expected.count() as u64);
+            }
+        }
+    }
+
+    /// A small xorshift generator, which is plenty for exercising the samplers.
+    fn xorshift(mut state: u64) -> impl FnMut() -> u64 {
+        move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        }
+    }
+
+    #[test]
+    fn shuffle_is_a_permutation() {
+        let mut values: Vec<u8> = (0..64).collect();
+        shuffle(&mut values, &mut xorshift(0x9E3779B97F4A7C15));
+
+        assert_ne!(values, (0..64).collect::<Vec<_>>());
+        values.sort();
+        assert_eq!(values, (0..64).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn sample_selects_distinct_boards_in_order() {
+        let values: Vec<u8> = (0..64).collect();
+
+        let selected = sample(&values, 5, &mut xorshift(42));
+        assert_eq!(selected.len(), 5);
+        assert!(selected.windows(2).all(|pair| pair[0] < pair[1]));
+
+        assert_eq!(sample(&values, 100, &mut xorshift(42)).len(), 64);
+    }
+
+    #[test]
+    fn stratified_sample_respects_strata() {
+        let values: Vec<u8> = (0..100).collect();
+        let strata = stratified_sample(&values, 3, |value| value % 4, &mut xorshift(7));
+
+        assert_eq!(strata.len(), 4);
+        for (key, selected) in strata {
+            assert_eq!(selected.len(), 3);
+            assert!(selected.iter().all(|value| *value % 4 == key));
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! Compass directions on an 8×8 board.
+//!
+//! North is the direction of increasing rank (adding 8 to an index), and east
+//! is the direction of increasing file (adding 1 to an index), so that with
+//! index 0 at a1 these directions agree with white's view of a chessboard.
+
+/// The mask of the a-file, i.e. the indices congruent to 0 mod 8.
+const FILE_A: u64 = 0x0101_0101_0101_0101;
+
+/// The mask of the h-file, i.e. the indices congruent to 7 mod 8.
+const FILE_H: u64 = 0x8080_8080_8080_8080;
+
+/// One of the eight compass directions.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Direction {
+    /// Towards the eighth rank.
+    North,
+    /// Towards the eighth rank and the h-file.
+    NorthEast,
+    /// Towards the h-file.
+    East,
+    /// Towards the first rank and the h-file.
+    SouthEast,
+    /// Towards the first rank.
+    South,
+    /// Towards the first rank and the a-file.
+    SouthWest,
+    /// Towards the a-file.
+    West,
+    /// Towards the eighth rank and the a-file.
+    NorthWest,
+}
+
+impl Direction {
+    /// All eight directions, clockwise from [`Direction::North`].
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// Returns the direction pointing the opposite way.
+    pub const fn opposite(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::NorthEast => Direction::SouthWest,
+            Direction::East => Direction::West,
+            Direction::SouthEast => Direction::NorthWest,
+            Direction::South => Direction::North,
+            Direction::SouthWest => Direction::NorthEast,
+            Direction::West => Direction::East,
+            Direction::NorthWest => Direction::SouthEast,
+        }
+    }
+
+    /// Returns the change in file and rank from one step in this direction.
+    pub const fn offset(self) -> (i8, i8) {
+        match self {
+            Direction::North => (0, 1),
+            Direction::NorthEast => (1, 1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, -1),
+            Direction::South => (0, -1),
+            Direction::SouthWest => (-1, -1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, 1),
+        }
+    }
+
+    /// Moves every set bit of `bits` one step in this direction, discarding
+    /// the bits which would leave the board rather than wrapping them
+    /// around to the opposite edge.
+    #[inline(always)]
+    pub const fn shift(self, bits: u64) -> u64 {
+        match self {
+            Direction::North => bits << 8,
+            Direction::NorthEast => (bits << 9) & !FILE_A,
+            Direction::East => (bits << 1) & !FILE_A,
+            Direction::SouthEast => (bits >> 7) & !FILE_A,
+            Direction::South => bits >> 8,
+            Direction::SouthWest => (bits >> 9) & !FILE_H,
+            Direction::West => (bits >> 1) & !FILE_H,
+            Direction::NorthWest => (bits << 7) & !FILE_H,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shifts_agree_with_offsets() {
+        for direction in Direction::ALL {
+            let (dx, dy) = direction.offset();
+
+            for square in 0..64i8 {
+                let (file, rank) = (square % 8 + dx, square / 8 + dy);
+                let expected = match (0..8).contains(&file) && (0..8).contains(&rank) {
+                    true => 1u64 << (8 * rank + file),
+                    false => 0,
+                };
+
+                assert_eq!(
+                    direction.shift(1 << square),
+                    expected,
+                    "{direction:?} from {square}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn opposite_directions_cancel() {
+        for direction in Direction::ALL {
+            let (dx, dy) = direction.offset();
+            assert_eq!(direction.opposite().offset(), (-dx, -dy));
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! Human-readable formatting of quadboards.
+
+use core::fmt;
+
+use halfling::Nibble;
+
+use crate::index::Index;
+use crate::Quadboard;
+
+/// A [`Display`](fmt::Display) adaptor for a [`Quadboard`], returned by
+/// [`Quadboard::display_with`].
+///
+/// The board is rendered as a grid with the eighth rank at the top and the
+/// a-file on the left, labelled with rank numbers on the left and file letters
+/// underneath, as in the following.
+///
+/// ```text
+/// 8 r n b q k b n r
+/// 7 p p p p p p p p
+/// 6 . . . . . . . .
+/// 5 . . . . . . . .
+/// 4 . . . . . . . .
+/// 3 . . . . . . . .
+/// 2 P P P P P P P P
+/// 1 R N B Q K B N R
+///   a b c d e f g h
+/// ```
+pub struct DisplayWith<'a, T, F> {
+    board: &'a Quadboard<T>,
+    render: F,
+}
+
+impl<'a, T, F> DisplayWith<'a, T, F> {
+    /// Creates an adaptor rendering each element of `board` with `render`.
+    pub fn new(board: &'a Quadboard<T>, render: F) -> Self {
+        Self { board, render }
+    }
+}
+
+impl<T, F, D> fmt::Display for DisplayWith<'_, T, F>
+where
+    Nibble: Into<T>,
+    F: Fn(T) -> D,
+    D: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for rank in (0..8u8).rev() {
+            write!(f, "{}", rank + 1)?;
+
+            for file in 0..8u8 {
+                let index = unsafe { Index::new_unchecked(8 * rank + file) };
+                write!(f, " {}", (self.render)(self.board.get(index)))?;
+            }
+
+            writeln!(f)?;
+        }
+
+        write!(f, " ")?;
+        for file in 'a'..='h' {
+            write!(f, " {file}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> fmt::Display for Quadboard<T>
+where
+    T: fmt::Display,
+    Nibble: Into<T>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display_with(|value| value).fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_with_renders_labelled_grid() {
+        let mut qb = Quadboard::<Nibble>::new_zeroed();
+        qb.set(0.try_into().unwrap(), Nibble::ONE);
+        qb.set(63.try_into().unwrap(), Nibble::TWO);
+
+        let rendered = qb
+            .display_with(|value| match value.get() {
+                0 => '.',
+                1 => 'x',
+                _ => 'o',
+            })
+            .to_string();
+
+        let expected = "\
+8 . . . . . . . o
+7 . . . . . . . .
+6 . . . . . . . .
+5 . . . . . . . .
+4 . . . . . . . .
+3 . . . . . . . .
+2 . . . . . . . .
+1 x . . . . . . .
+  a b c d e f g h";
+
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn display_uses_element_display() {
+        let qb = Quadboard::from_fn(|index| Nibble::try_from(index.get() % 10).unwrap());
+        let rendered = qb.to_string();
+
+        assert!(rendered.starts_with("8 6 7 8 9 0 1 2 3\n"));
+        assert!(rendered.contains("\n1 0 1 2 3 4 5 6 7\n"));
+    }
+}
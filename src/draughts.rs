@@ -0,0 +1,192 @@
+//! Support for draughts (checkers) on the standard 8×8 board.
+//!
+//! Squares are indexed from 0 at a1 (white's bottom-left corner) to 63 at h8,
+//! rank by rank, so that play happens on the squares whose rank and file have
+//! the same parity. White moves up the board and Black moves down it.
+
+use halfling::Nibble;
+
+use crate::codec::EmptySquare;
+use crate::index::Index;
+use crate::Quadboard;
+
+/// The mask of the 32 dark (playable) squares.
+pub const DARK_SQUARES: u64 = 0xAA55_AA55_AA55_AA55;
+
+/// The mask of the first rank, i.e. indices 0 through 7.
+const FIRST_RANK: u64 = 0x0000_0000_0000_00FF;
+
+/// The mask of the eighth rank, i.e. indices 56 through 63.
+const EIGHTH_RANK: u64 = 0xFF00_0000_0000_0000;
+
+/// The side to which a piece belongs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// The side which starts on ranks 1 through 3.
+    White,
+    /// The side which starts on ranks 6 through 8.
+    Black,
+}
+
+/// The contents of a single square of a draughts board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(u8)]
+pub enum Square {
+    /// An unoccupied square.
+    #[default]
+    Empty = 0b000,
+    /// A white man.
+    WhiteMan = 0b001,
+    /// A white king.
+    WhiteKing = 0b011,
+    /// A black man.
+    BlackMan = 0b101,
+    /// A black king.
+    BlackKing = 0b111,
+}
+
+/// The error produced when a [`Nibble`] does not encode a [`Square`].
+#[derive(Debug)]
+pub struct InvalidSquareError(pub Nibble);
+
+impl core::fmt::Display for InvalidSquareError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "The nibble {} does not encode a draughts square.",
+            self.0
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidSquareError {}
+
+impl From<Square> for Nibble {
+    fn from(value: Square) -> Self {
+        unsafe { Nibble::new_unchecked(value as u8) }
+    }
+}
+
+impl TryFrom<Nibble> for Square {
+    type Error = InvalidSquareError;
+
+    fn try_from(value: Nibble) -> Result<Self, Self::Error> {
+        match value.get() {
+            0b000 => Ok(Square::Empty),
+            0b001 => Ok(Square::WhiteMan),
+            0b011 => Ok(Square::WhiteKing),
+            0b101 => Ok(Square::BlackMan),
+            0b111 => Ok(Square::BlackKing),
+            _ => Err(InvalidSquareError(value)),
+        }
+    }
+}
+
+impl EmptySquare for Square {}
+
+impl Square {
+    /// Returns the man of the given color.
+    pub const fn man(color: Color) -> Self {
+        match color {
+            Color::White => Square::WhiteMan,
+            Color::Black => Square::BlackMan,
+        }
+    }
+
+    /// Returns the king of the given color.
+    pub const fn king(color: Color) -> Self {
+        match color {
+            Color::White => Square::WhiteKing,
+            Color::Black => Square::BlackKing,
+        }
+    }
+
+    /// Returns the color of the piece on this square, if any.
+    pub const fn color(self) -> Option<Color> {
+        match self {
+            Square::Empty => None,
+            Square::WhiteMan | Square::WhiteKing => Some(Color::White),
+            Square::BlackMan | Square::BlackKing => Some(Color::Black),
+        }
+    }
+}
+
+/// Returns the mask of the rank on which men of the given color promote.
+pub const fn promotion_rank(color: Color) -> u64 {
+    match color {
+        Color::White => EIGHTH_RANK,
+        Color::Black => FIRST_RANK,
+    }
+}
+
+/// Returns an iterator over the dark squares, in increasing order.
+pub fn dark_squares() -> impl Iterator<Item = Index> {
+    (0..64u8)
+        .filter(|i| DARK_SQUARES & (1 << i) != 0)
+        .map(|i| unsafe { Index::new_unchecked(i) })
+}
+
+/// Returns the standard starting position, with twelve men per side on the
+/// dark squares of their first three ranks.
+pub fn start_position() -> Quadboard<Square> {
+    let mut board = Quadboard::new_zeroed();
+    board.inner.fill_masked(
+        DARK_SQUARES & 0x0000_0000_00FF_FFFF,
+        Square::WhiteMan.into(),
+    );
+    board.inner.fill_masked(
+        DARK_SQUARES & 0xFFFF_FF00_0000_0000,
+        Square::BlackMan.into(),
+    );
+    board
+}
+
+/// Crowns every man standing on its promotion rank, returning the mask of
+/// the squares that were promoted.
+pub fn promote(board: &mut Quadboard<Square>) -> u64 {
+    let white = board.mask_of(Square::WhiteMan) & promotion_rank(Color::White);
+    let black = board.mask_of(Square::BlackMan) & promotion_rank(Color::Black);
+
+    board.inner.fill_masked(white, Square::WhiteKing.into());
+    board.inner.fill_masked(black, Square::BlackKing.into());
+    white | black
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square_at(board: &Quadboard<Square>, index: u8) -> Square {
+        board
+            .as_raw_quadboard()
+            .get(index.try_into().unwrap())
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn start_position_is_standard() {
+        let board = start_position();
+
+        assert_eq!(board.mask_of(Square::WhiteMan).count_ones(), 12);
+        assert_eq!(board.mask_of(Square::BlackMan).count_ones(), 12);
+        assert_eq!(square_at(&board, 0), Square::WhiteMan);
+        assert_eq!(square_at(&board, 1), Square::Empty);
+        assert_eq!(square_at(&board, 63), Square::BlackMan);
+        assert_eq!(dark_squares().count(), 32);
+    }
+
+    #[test]
+    fn promote_crowns_men_on_the_far_rank() {
+        let mut board = Quadboard::new_zeroed();
+        board.set(56.try_into().unwrap(), Square::WhiteMan);
+        board.set(58.try_into().unwrap(), Square::BlackMan);
+        board.set(1.try_into().unwrap(), Square::BlackMan);
+
+        assert_eq!(promote(&mut board), (1 << 56) | (1 << 1));
+        assert_eq!(square_at(&board, 56), Square::WhiteKing);
+        assert_eq!(square_at(&board, 58), Square::BlackMan);
+        assert_eq!(square_at(&board, 1), Square::BlackKing);
+    }
+}
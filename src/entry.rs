@@ -0,0 +1,82 @@
+//! In-place access to a single element of a quadboard.
+
+use halfling::Nibble;
+
+use crate::index::Index;
+use crate::Quadboard;
+
+/// A view into the element at a single [`Index`] of a mutably borrowed
+/// [`Quadboard`], returned by [`Quadboard::entry`].
+///
+/// Since the index is validated once when the entry is created, reading and
+/// writing through an entry never repeats the bounds check.
+#[derive(Debug)]
+pub struct Entry<'a, T> {
+    board: &'a mut Quadboard<T>,
+    index: Index,
+}
+
+impl<'a, T> Entry<'a, T> {
+    /// Creates an entry for the element of `board` at `index`.
+    pub fn new(board: &'a mut Quadboard<T>, index: Index) -> Self {
+        Self { board, index }
+    }
+
+    /// Returns the [`Index`] of this entry.
+    #[inline(always)]
+    pub fn index(&self) -> Index {
+        self.index
+    }
+
+    /// Returns the value of this entry.
+    #[inline(always)]
+    pub fn get(&self) -> T
+    where
+        Nibble: Into<T>,
+    {
+        unsafe { self.board.get_unchecked(self.index.get()) }
+    }
+
+    /// Overwrites the value of this entry with `value`.
+    #[inline(always)]
+    pub fn set(&mut self, value: T)
+    where
+        T: Into<Nibble>,
+    {
+        unsafe { self.board.set_unchecked(self.index.get(), value) }
+    }
+
+    /// Replaces the value of this entry with the result of calling `f` on it.
+    #[inline(always)]
+    pub fn modify(&mut self, f: impl FnOnce(T) -> T) -> &mut Self
+    where
+        T: Into<Nibble>,
+        Nibble: Into<T>,
+    {
+        let value = f(self.get());
+        self.set(value);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_reads_and_writes_through() {
+        let index = 33.try_into().unwrap();
+        let mut qb = Quadboard::<Nibble>::new_zeroed();
+
+        let mut entry = qb.entry(index);
+        assert_eq!(entry.get(), Nibble::ZERO);
+        entry.set(Nibble::FOUR);
+        entry
+            .modify(|value| unsafe { Nibble::new_unchecked(value.get() * 2) })
+            .modify(|value| unsafe { Nibble::new_unchecked(value.get() + 1) });
+        assert_eq!(entry.index(), index);
+
+        assert_eq!(qb.get(index), Nibble::NINE);
+        assert_eq!(qb.as_raw_quadboard().occupancy(), 1 << 33);
+    }
+}
@@ -1,6 +1,6 @@
 //! Correct-by-construction indices into quadboards.
 
-use thiserror::Error;
+use crate::iter::{IndexRange, Indices};
 
 /// The set of possible values that an [`Index`] may take, ranging
 /// from 0 (inclusive) to 64 (exclusive).
@@ -9,7 +9,7 @@ use thiserror::Error;
 /// can apply the niche value optimisation, and which has no practical
 /// runtime cost (compared to something like the `nonmax` crate, for
 /// example).
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 #[repr(u8)]
 enum AllowedIndexValue {
     _00,
@@ -92,15 +92,70 @@ enum AllowedIndexValue {
 ///     std::mem::size_of::<Index>()
 /// )
 /// ```
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+///
+/// # Layout
+/// An [`Index`] has the same size, alignment, and ABI as a `u8`, and so
+/// it can always be reinterpreted as one. The converse only holds for
+/// bytes strictly less than 64; every other bit pattern is invalid.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 #[repr(transparent)]
 pub struct Index(AllowedIndexValue);
 
 /// The unit error produced when a numeric conversion into an [`Index`] fails.
-#[derive(Debug, Error)]
-#[error("Attempted to construct an Index with a value greater than 63.")]
+#[derive(Debug)]
 pub struct InvalidIndexError;
 
+impl core::fmt::Display for InvalidIndexError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Attempted to construct an Index with a value greater than 63."
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidIndexError {}
+
+/// The unit error produced when parsing an [`Index`] from a string fails.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseIndexError;
+
+impl core::fmt::Display for ParseIndexError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Attempted to parse an Index from a string which is not a square such as e4."
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseIndexError {}
+
+/// Parses a square in algebraic notation, i.e. a lowercase file letter
+/// followed by a rank digit, as in `"e4"`.
+impl core::str::FromStr for Index {
+    type Err = ParseIndexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match *s.as_bytes() {
+            [file @ b'a'..=b'h', rank @ b'1'..=b'8'] => Ok(Index::from_coords(
+                File::ALL[(file - b'a') as usize],
+                Rank::ALL[(rank - b'1') as usize],
+            )),
+            _ => Err(ParseIndexError),
+        }
+    }
+}
+
+/// Formats an [`Index`] in algebraic notation, as in `"e4"`.
+impl core::fmt::Display for Index {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}{}", self.file(), self.rank())
+    }
+}
+
 impl TryFrom<u8> for Index {
     type Error = InvalidIndexError;
 
@@ -112,12 +167,21 @@ impl TryFrom<u8> for Index {
     }
 }
 
-impl From<Index> for u8 {
-    fn from(value: Index) -> Self {
-        value.get()
-    }
+/// Generates lossless conversions from [`Index`] into wider integer types.
+macro_rules! index_into_impls {
+    ($($target:ty),+) => {
+        $(
+            impl From<Index> for $target {
+                fn from(value: Index) -> Self {
+                    value.get() as $target
+                }
+            }
+        )+
+    };
 }
 
+index_into_impls!(u8, u16, u32, u64, usize);
+
 impl Index {
     /// The minimum value representable by an [`Index`], i.e. 0.
     pub const MIN: Self = Index(AllowedIndexValue::_00);
@@ -137,7 +201,7 @@ impl Index {
     /// `value` must be less than 64.
     pub const unsafe fn new_unchecked(value: u8) -> Self {
         debug_assert!(value < 64);
-        unsafe { std::mem::transmute(value) }
+        unsafe { core::mem::transmute(value) }
     }
 
     /// Checks whether `value` can be safely converted into
@@ -154,6 +218,211 @@ impl Index {
         // must necessarily have 0 as its two upper bits.
         (value & 0b11000000) == 0u8
     }
+
+    /// Converts `value` into an [`Index`], failing if it is greater than 63.
+    ///
+    /// This is provided as an inherent method rather than as another
+    /// [`TryFrom`] impl, since a second impl would stop integer literals in
+    /// expressions like `12.try_into()` from being inferred as `u8`.
+    #[inline(always)]
+    pub const fn from_usize(value: usize) -> Result<Self, InvalidIndexError> {
+        match value < 64 {
+            true => Ok(unsafe { Self::new_unchecked(value as u8) }),
+            false => Err(InvalidIndexError),
+        }
+    }
+
+    /// Returns an iterator over all 64 indices, in increasing order.
+    #[inline(always)]
+    pub const fn all() -> Indices {
+        Indices::new(u64::MAX)
+    }
+
+    /// Returns an iterator over the indices from `start` (inclusive) to
+    /// `end` (exclusive), in increasing order.
+    #[inline(always)]
+    pub const fn range(start: Index, end: Index) -> IndexRange {
+        IndexRange::new(start, end)
+    }
+
+    /// Returns the [`Index`] `rhs` places after `self`, or [`None`] if it
+    /// would be greater than 63.
+    #[inline(always)]
+    pub const fn checked_add(&self, rhs: u8) -> Option<Self> {
+        match self.get().checked_add(rhs) {
+            Some(value) if Self::is_valid_index(value) => {
+                Some(unsafe { Self::new_unchecked(value) })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the [`Index`] `rhs` places before `self`, or [`None`] if it
+    /// would be less than 0.
+    #[inline(always)]
+    pub const fn checked_sub(&self, rhs: u8) -> Option<Self> {
+        match self.get().checked_sub(rhs) {
+            Some(value) => Some(unsafe { Self::new_unchecked(value) }),
+            None => None,
+        }
+    }
+
+    /// Returns the [`Index`] `dx` files and `dy` ranks away from `self`, or
+    /// [`None`] if that square is not on the board.
+    ///
+    /// Unlike adding `8 * dy + dx` to the value of `self`, this never wraps
+    /// around from one edge of the board to the other.
+    #[inline(always)]
+    pub const fn offset(&self, dx: i8, dy: i8) -> Option<Self> {
+        let file = (self.get() % 8) as i16 + dx as i16;
+        let rank = (self.get() / 8) as i16 + dy as i16;
+
+        match file >= 0 && file < 8 && rank >= 0 && rank < 8 {
+            true => Some(unsafe { Self::new_unchecked((8 * rank + file) as u8) }),
+            false => None,
+        }
+    }
+
+    /// Returns the [`Index`] of the square at the intersection of `file`
+    /// and `rank`.
+    #[inline(always)]
+    pub const fn from_coords(file: File, rank: Rank) -> Self {
+        unsafe { Self::new_unchecked(8 * rank.get() + file.get()) }
+    }
+
+    /// Returns the [`File`] containing `self`.
+    #[inline(always)]
+    pub const fn file(&self) -> File {
+        File::ALL[(self.get() % 8) as usize]
+    }
+
+    /// Returns the [`Rank`] containing `self`.
+    #[inline(always)]
+    pub const fn rank(&self) -> Rank {
+        Rank::ALL[(self.get() / 8) as usize]
+    }
+}
+
+/// A file (column) of an 8×8 board, where the file of an [`Index`]
+/// is its value modulo 8.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[repr(u8)]
+pub enum File {
+    /// The file of indices 0, 8, ..., 56.
+    A,
+    /// The file of indices 1, 9, ..., 57.
+    B,
+    /// The file of indices 2, 10, ..., 58.
+    C,
+    /// The file of indices 3, 11, ..., 59.
+    D,
+    /// The file of indices 4, 12, ..., 60.
+    E,
+    /// The file of indices 5, 13, ..., 61.
+    F,
+    /// The file of indices 6, 14, ..., 62.
+    G,
+    /// The file of indices 7, 15, ..., 63.
+    H,
+}
+
+impl File {
+    /// All eight files, from [`File::A`] to [`File::H`].
+    pub const ALL: [File; 8] = [
+        File::A,
+        File::B,
+        File::C,
+        File::D,
+        File::E,
+        File::F,
+        File::G,
+        File::H,
+    ];
+
+    /// Returns the value of `self` as a `u8`, from 0 for [`File::A`]
+    /// to 7 for [`File::H`].
+    pub const fn get(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Returns a bitmask of the indices in this file.
+    pub const fn mask(&self) -> u64 {
+        0x0101_0101_0101_0101 << self.get()
+    }
+
+    /// Returns an iterator over the indices in this file, in increasing order.
+    #[inline(always)]
+    pub const fn squares(&self) -> Indices {
+        Indices::new(self.mask())
+    }
+}
+
+/// Formats a [`File`] as its lowercase letter.
+impl core::fmt::Display for File {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", (b'a' + self.get()) as char)
+    }
+}
+
+/// A rank (row) of an 8×8 board, where the rank of an [`Index`]
+/// is its value divided by 8.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[repr(u8)]
+pub enum Rank {
+    /// The rank of indices 0 to 7.
+    First,
+    /// The rank of indices 8 to 15.
+    Second,
+    /// The rank of indices 16 to 23.
+    Third,
+    /// The rank of indices 24 to 31.
+    Fourth,
+    /// The rank of indices 32 to 39.
+    Fifth,
+    /// The rank of indices 40 to 47.
+    Sixth,
+    /// The rank of indices 48 to 55.
+    Seventh,
+    /// The rank of indices 56 to 63.
+    Eighth,
+}
+
+impl Rank {
+    /// All eight ranks, from [`Rank::First`] to [`Rank::Eighth`].
+    pub const ALL: [Rank; 8] = [
+        Rank::First,
+        Rank::Second,
+        Rank::Third,
+        Rank::Fourth,
+        Rank::Fifth,
+        Rank::Sixth,
+        Rank::Seventh,
+        Rank::Eighth,
+    ];
+
+    /// Returns the value of `self` as a `u8`, from 0 for [`Rank::First`]
+    /// to 7 for [`Rank::Eighth`].
+    pub const fn get(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Returns a bitmask of the indices in this rank.
+    pub const fn mask(&self) -> u64 {
+        0xFF << (8 * self.get())
+    }
+
+    /// Returns an iterator over the indices in this rank, in increasing order.
+    #[inline(always)]
+    pub const fn squares(&self) -> Indices {
+        Indices::new(self.mask())
+    }
+}
+
+/// Formats a [`Rank`] as its digit, from 1 to 8.
+impl core::fmt::Display for Rank {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.get() + 1)
+    }
 }
 
 #[cfg(test)]
@@ -165,6 +434,116 @@ mod tests {
         assert_eq!(std::mem::size_of::<Index>(), 1);
     }
 
+    #[test]
+    fn index_has_the_layout_of_u8() {
+        assert_eq!(std::mem::align_of::<Index>(), std::mem::align_of::<u8>());
+
+        for value in 0..64u8 {
+            let index: Index = unsafe { std::mem::transmute(value) };
+            assert_eq!(index.get(), value);
+            assert_eq!(unsafe { std::mem::transmute::<Index, u8>(index) }, value);
+        }
+    }
+
+    #[test]
+    fn coordinates_round_trip() {
+        for value in 0..64u8 {
+            let index = Index::try_from(value).unwrap();
+            assert_eq!(index.file().get(), value % 8);
+            assert_eq!(index.rank().get(), value / 8);
+            assert_eq!(Index::from_coords(index.file(), index.rank()), index);
+            assert_ne!(index.file().mask() & index.rank().mask() & (1 << value), 0);
+        }
+
+        assert_eq!(Index::from_coords(File::E, Rank::Fourth).get(), 28);
+    }
+
+    #[test]
+    fn algebraic_notation_round_trips() {
+        for value in 0..64u8 {
+            let index = Index::try_from(value).unwrap();
+            assert_eq!(index.to_string().parse::<Index>(), Ok(index));
+        }
+
+        assert_eq!("a1".parse::<Index>().map(|index| index.get()), Ok(0));
+        assert_eq!("e4".parse::<Index>().map(|index| index.get()), Ok(28));
+        assert_eq!(Index::MAX.to_string(), "h8");
+    }
+
+    #[test]
+    fn algebraic_notation_rejects_invalid_squares() {
+        for s in ["", "e", "e9", "i4", "E4", "e4 ", "4e", "e10"] {
+            assert_eq!(s.parse::<Index>(), Err(ParseIndexError), "{s:?}");
+        }
+    }
+
+    #[test]
+    fn checked_arithmetic_stays_in_range() {
+        assert_eq!(Index::MIN.checked_add(63), Some(Index::MAX));
+        assert_eq!(Index::MIN.checked_add(64), None);
+        assert_eq!(Index::MAX.checked_add(255), None);
+        assert_eq!(Index::MAX.checked_sub(63), Some(Index::MIN));
+        assert_eq!(Index::MIN.checked_sub(1), None);
+    }
+
+    #[test]
+    fn offset_does_not_wrap_across_edges() {
+        let h1 = Index::try_from(7).unwrap();
+        assert_eq!(h1.offset(1, 0), None);
+        assert_eq!(h1.offset(-1, 2).map(|index| index.get()), Some(22));
+        assert_eq!(h1.offset(0, -1), None);
+
+        // a knight on b1 has exactly three moves
+        let b1 = Index::try_from(1).unwrap();
+        let jumps = [
+            (1, 2),
+            (2, 1),
+            (2, -1),
+            (1, -2),
+            (-1, -2),
+            (-2, -1),
+            (-2, 1),
+            (-1, 2),
+        ];
+        let targets: Vec<u8> = jumps
+            .iter()
+            .filter_map(|&(dx, dy)| b1.offset(dx, dy))
+            .map(|index| index.get())
+            .collect();
+        assert_eq!(targets, vec![18, 11, 16]);
+    }
+
+    #[test]
+    fn offset_handles_extreme_offsets() {
+        for corner in [0, 7, 56, 63].map(|value| Index::try_from(value).unwrap()) {
+            for (dx, dy) in [(i8::MAX, 0), (0, i8::MAX), (i8::MIN, 0), (0, i8::MIN)] {
+                assert_eq!(corner.offset(dx, dy), None);
+            }
+        }
+    }
+
+    #[test]
+    fn square_iterators_visit_expected_indices() {
+        assert!(Index::all().map(|index| index.get()).eq(0..64));
+        assert!(File::C.squares().all(|index| index.file() == File::C));
+        assert!(Rank::Fifth.squares().map(|index| index.get()).eq(32..40));
+        assert_eq!(Rank::ALL.iter().flat_map(Rank::squares).count(), 64);
+    }
+
+    #[test]
+    fn integer_conversions_preserve_value() {
+        for value in 0..64usize {
+            let index = Index::from_usize(value).unwrap();
+            assert_eq!(usize::from(index), value);
+            assert_eq!(u16::from(index), value as u16);
+            assert_eq!(u32::from(index), value as u32);
+            assert_eq!(u64::from(index), value as u64);
+        }
+
+        assert!(Index::from_usize(64).is_err());
+        assert!(Index::from_usize(usize::MAX).is_err());
+    }
+
     #[test]
     fn niche_value_optimisation_applies_to_index() {
         assert_eq!(
@@ -0,0 +1,178 @@
+//! Deduplicated, shared storage for large numbers of boards.
+//!
+//! A [`BoardInterner`] stores each distinct [`RawQuadboard`] exactly once,
+//! handing out [`SharedBoard`] handles which are cheap to clone and which
+//! usually compare equal by pointer alone.
+
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::raw_quadboard::RawQuadboard;
+
+/// Computes a 64-bit fingerprint of `board`.
+///
+/// Fingerprints are stable across runs and platforms, but distinct boards
+/// may share a fingerprint; they are intended for bucketing, not identity.
+pub const fn fingerprint(board: &RawQuadboard) -> u64 {
+    let channels = board.as_channels();
+    let mut hash = 0x9E37_79B9_7F4A_7C15u64;
+    let mut i = 0;
+
+    while i < 4 {
+        // the splitmix64 finaliser, applied to each channel in turn
+        let mut z = hash ^ channels[i];
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        hash = z ^ (z >> 31);
+        i += 1;
+    }
+
+    hash
+}
+
+/// A shared handle to an interned [`RawQuadboard`].
+#[derive(Debug, Clone)]
+pub struct SharedBoard {
+    board: Arc<RawQuadboard>,
+    fingerprint: u64,
+}
+
+impl SharedBoard {
+    /// Returns the fingerprint of the underlying board.
+    pub const fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// Returns `true` if `self` and `other` point to the same allocation,
+    /// which is always the case for equal boards from the same interner.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.board, &other.board)
+    }
+}
+
+impl Deref for SharedBoard {
+    type Target = RawQuadboard;
+
+    fn deref(&self) -> &Self::Target {
+        &self.board
+    }
+}
+
+impl PartialEq for SharedBoard {
+    fn eq(&self, other: &Self) -> bool {
+        // handles from different interners may still hold equal boards
+        self.ptr_eq(other) || (self.fingerprint == other.fingerprint && self.board == other.board)
+    }
+}
+
+impl Eq for SharedBoard {}
+
+/// A deduplicating store of [`RawQuadboard`]s.
+#[derive(Debug, Default, Clone)]
+pub struct BoardInterner {
+    buckets: HashMap<u64, Vec<SharedBoard>>,
+    len: usize,
+}
+
+impl BoardInterner {
+    /// Creates a new, empty [`BoardInterner`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of distinct boards in the interner.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the interner contains no boards.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns a handle to the interned copy of `board`, inserting it if
+    /// it is not already present.
+    pub fn intern(&mut self, board: RawQuadboard) -> SharedBoard {
+        let fingerprint = fingerprint(&board);
+        let bucket = self.buckets.entry(fingerprint).or_default();
+
+        if let Some(shared) = bucket.iter().find(|shared| *shared.board == board) {
+            return shared.clone();
+        }
+
+        let shared = SharedBoard {
+            board: Arc::new(board),
+            fingerprint,
+        };
+
+        bucket.push(shared.clone());
+        self.len += 1;
+        shared
+    }
+
+    /// Returns a handle to the interned copy of `board`, if there is one.
+    pub fn get(&self, board: &RawQuadboard) -> Option<SharedBoard> {
+        self.get_by_fingerprint(fingerprint(board))
+            .find(|shared| *shared.board == *board)
+            .cloned()
+    }
+
+    /// Returns an iterator over the interned boards with the given fingerprint.
+    pub fn get_by_fingerprint(&self, fingerprint: u64) -> impl Iterator<Item = &SharedBoard> {
+        self.buckets.get(&fingerprint).into_iter().flatten()
+    }
+
+    /// Returns an iterator over the distinct interned boards, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &SharedBoard> {
+        self.buckets.values().flatten()
+    }
+
+    /// Removes every board which is not referenced by any outstanding
+    /// [`SharedBoard`], returning the number of boards removed.
+    pub fn purge(&mut self) -> usize {
+        let before = self.len;
+
+        self.buckets.retain(|_, bucket| {
+            bucket.retain(|shared| Arc::strong_count(&shared.board) > 1);
+            !bucket.is_empty()
+        });
+
+        self.len = self.buckets.values().map(Vec::len).sum();
+        before - self.len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halfling::Nibble;
+
+    #[test]
+    fn interner_deduplicates_equal_boards() {
+        let mut interner = BoardInterner::new();
+        let a = interner.intern(RawQuadboard::splat(Nibble::ONE));
+        let b = interner.intern(RawQuadboard::splat(Nibble::ONE));
+        let c = interner.intern(RawQuadboard::splat(Nibble::TWO));
+
+        assert!(a.ptr_eq(&b));
+        assert_ne!(a, c);
+        assert_eq!(interner.len(), 2);
+        assert_eq!(interner.iter().count(), 2);
+
+        let found = interner.get(&RawQuadboard::splat(Nibble::TWO)).unwrap();
+        assert!(found.ptr_eq(&c));
+        assert_eq!(interner.get_by_fingerprint(a.fingerprint()).count(), 1);
+    }
+
+    #[test]
+    fn interner_purges_unreferenced_boards() {
+        let mut interner = BoardInterner::new();
+        let kept = interner.intern(RawQuadboard::splat(Nibble::ONE));
+        interner.intern(RawQuadboard::splat(Nibble::TWO));
+
+        assert_eq!(interner.purge(), 1);
+        assert_eq!(interner.len(), 1);
+        assert!(interner.get(&kept).is_some());
+    }
+}
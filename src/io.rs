@@ -0,0 +1,307 @@
+//! Streaming encoders and decoders for sequences of boards.
+//!
+//! A board stream is a sequence of boards in their canonical encoding (see
+//! [`RawQuadboard::to_le_bytes`]), optionally preceded by the number of boards
+//! as a little-endian `u64`, and optionally with each board followed by its
+//! little-endian CRC-32 checksum (as in a [`VerifiedBoard`]).
+//!
+//! Neither [`BoardReader`] nor [`BoardWriter`] buffer their inner reader or
+//! writer, so you will usually want to wrap files in [`std::io::BufReader`] or
+//! [`std::io::BufWriter`] before handing them over.
+//!
+//! For random access into large streams, the `board-file` feature provides
+//! `BoardFile`, which borrows boards directly out of any byte storage. It is
+//! defined in the `board_file` module and re-exported here.
+
+use std::io::{self, Read, Write};
+
+#[cfg(feature = "board-file")]
+pub use crate::board_file::{BoardFile, InvalidBoardFileError, QuadboardRef};
+use crate::checksum::VerifiedBoard;
+use crate::raw_quadboard::RawQuadboard;
+
+/// The layout of a board stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StreamFormat {
+    /// Whether the stream begins with the number of boards it contains.
+    pub length_prefix: bool,
+    /// Whether each board is followed by its CRC-32 checksum.
+    pub checksum: bool,
+}
+
+impl StreamFormat {
+    /// Returns the number of bytes used to encode each board.
+    pub(crate) const fn board_len(&self) -> usize {
+        match self.checksum {
+            true => VerifiedBoard::ENCODED_LEN,
+            false => 32,
+        }
+    }
+}
+
+/// A lazy iterator over the boards in a board stream.
+///
+/// Each item is either a board or the I/O error encountered while reading
+/// it; checksum failures are reported as errors of kind
+/// [`io::ErrorKind::InvalidData`]. After an error has been returned the
+/// reader should be considered exhausted.
+#[derive(Debug)]
+pub struct BoardReader<R> {
+    inner: R,
+    format: StreamFormat,
+    remaining: Option<u64>,
+}
+
+impl<R: Read> BoardReader<R> {
+    /// Creates a new [`BoardReader`], reading the length prefix from `inner`
+    /// immediately if `format` includes one.
+    pub fn new(mut inner: R, format: StreamFormat) -> io::Result<Self> {
+        let remaining = match format.length_prefix {
+            true => {
+                let mut prefix = [0u8; 8];
+                inner.read_exact(&mut prefix)?;
+                Some(u64::from_le_bytes(prefix))
+            }
+            false => None,
+        };
+
+        Ok(Self {
+            inner,
+            format,
+            remaining,
+        })
+    }
+
+    /// Returns the number of boards left in the stream, if it was length-prefixed.
+    pub const fn remaining(&self) -> Option<u64> {
+        self.remaining
+    }
+
+    /// Consumes `self` and returns the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Reads the next encoded board into `buf`, returning `Ok(false)` if the
+    /// stream ended cleanly before the first byte.
+    fn read_board_bytes(&mut self, buf: &mut [u8]) -> io::Result<bool> {
+        let mut filled = 0;
+
+        while filled < buf.len() {
+            match self.inner.read(&mut buf[filled..]) {
+                Ok(0) if filled == 0 && self.remaining.is_none() => return Ok(false),
+                Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+                Ok(n) => filled += n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Reads and decodes the next board, if any.
+    fn read_board(&mut self) -> io::Result<Option<RawQuadboard>> {
+        if self.remaining == Some(0) {
+            return Ok(None);
+        }
+
+        let mut buf = [0u8; VerifiedBoard::ENCODED_LEN];
+        let len = self.format.board_len();
+
+        if !self.read_board_bytes(&mut buf[..len])? {
+            return Ok(None);
+        }
+
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= 1;
+        }
+
+        match self.format.checksum {
+            true => VerifiedBoard::from_bytes(&buf)
+                .map(|verified| Some(verified.board()))
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+            false => Ok(Some(RawQuadboard::from_le_bytes(
+                buf[..32].try_into().unwrap(),
+            ))),
+        }
+    }
+}
+
+impl<R: Read> Iterator for BoardReader<R> {
+    type Item = io::Result<RawQuadboard>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.read_board() {
+            Ok(board) => board.map(Ok),
+            Err(err) => {
+                self.remaining = Some(0);
+                Some(Err(err))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining {
+            Some(remaining) => (0, usize::try_from(remaining).ok()),
+            None => (0, None),
+        }
+    }
+}
+
+/// An incremental writer of board streams.
+///
+/// Call [`BoardWriter::finish`] once all the boards have been written to
+/// flush the inner writer and check that the length prefix (if any) was
+/// honoured.
+#[derive(Debug)]
+pub struct BoardWriter<W> {
+    inner: W,
+    format: StreamFormat,
+    remaining: Option<u64>,
+}
+
+impl<W: Write> BoardWriter<W> {
+    /// Creates a new [`BoardWriter`] for a stream without a length prefix.
+    pub fn new(inner: W, checksum: bool) -> Self {
+        Self {
+            inner,
+            format: StreamFormat {
+                length_prefix: false,
+                checksum,
+            },
+            remaining: None,
+        }
+    }
+
+    /// Creates a new [`BoardWriter`] for a stream of exactly `len` boards,
+    /// writing the length prefix to `inner` immediately.
+    pub fn with_length_prefix(mut inner: W, checksum: bool, len: u64) -> io::Result<Self> {
+        inner.write_all(&len.to_le_bytes())?;
+
+        Ok(Self {
+            inner,
+            format: StreamFormat {
+                length_prefix: true,
+                checksum,
+            },
+            remaining: Some(len),
+        })
+    }
+
+    /// Returns the format of the stream being written, which should be used
+    /// to construct a [`BoardReader`] for it.
+    pub const fn format(&self) -> StreamFormat {
+        self.format
+    }
+
+    /// Writes `board` to the stream.
+    ///
+    /// This fails with [`io::ErrorKind::InvalidInput`] if the stream is
+    /// length-prefixed and already contains the declared number of boards.
+    pub fn write_board(&mut self, board: &RawQuadboard) -> io::Result<()> {
+        if let Some(remaining) = self.remaining.as_mut() {
+            match remaining.checked_sub(1) {
+                Some(value) => *remaining = value,
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "Attempted to write more boards than the length prefix allows.",
+                    ))
+                }
+            }
+        }
+
+        match self.format.checksum {
+            true => self.inner.write_all(&VerifiedBoard::new(*board).to_bytes()),
+            false => self.inner.write_all(&board.to_le_bytes()),
+        }
+    }
+
+    /// Flushes the stream and returns the inner writer.
+    ///
+    /// This fails with [`io::ErrorKind::InvalidInput`] if the stream is
+    /// length-prefixed and fewer boards were written than were declared.
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(remaining @ 1..) = self.remaining {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("The board stream is missing {remaining} boards."),
+            ));
+        }
+
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halfling::Nibble;
+
+    fn sample_boards() -> Vec<RawQuadboard> {
+        (0..5u8)
+            .map(|i| {
+                let mut board = RawQuadboard::splat(Nibble::try_from(i).unwrap());
+                board.set((i * 7).try_into().unwrap(), Nibble::MAX);
+                board
+            })
+            .collect()
+    }
+
+    #[test]
+    fn board_streams_round_trip_in_every_format() {
+        let boards = sample_boards();
+
+        for checksum in [false, true] {
+            for length_prefix in [false, true] {
+                let mut writer = match length_prefix {
+                    true => BoardWriter::with_length_prefix(Vec::new(), checksum, 5).unwrap(),
+                    false => BoardWriter::new(Vec::new(), checksum),
+                };
+
+                for board in &boards {
+                    writer.write_board(board).unwrap();
+                }
+
+                let format = writer.format();
+                let bytes = writer.finish().unwrap();
+                let reader = BoardReader::new(bytes.as_slice(), format).unwrap();
+                let decoded: Vec<_> = reader.collect::<io::Result<_>>().unwrap();
+
+                assert_eq!(decoded, boards);
+            }
+        }
+    }
+
+    #[test]
+    fn board_reader_reports_corruption_and_truncation() {
+        let mut writer = BoardWriter::new(Vec::new(), true);
+        for board in &sample_boards() {
+            writer.write_board(board).unwrap();
+        }
+
+        let format = writer.format();
+        let mut bytes = writer.finish().unwrap();
+        bytes[40] ^= 1;
+        bytes.truncate(bytes.len() - 1);
+
+        let mut reader = BoardReader::new(bytes.as_slice(), format).unwrap();
+        assert!(reader.next().unwrap().is_ok());
+
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn board_writer_enforces_length_prefix() {
+        let mut writer = BoardWriter::with_length_prefix(Vec::new(), false, 1).unwrap();
+        writer.write_board(&RawQuadboard::default()).unwrap();
+        assert!(writer.write_board(&RawQuadboard::default()).is_err());
+
+        let writer = BoardWriter::with_length_prefix(Vec::new(), false, 1).unwrap();
+        assert!(writer.finish().is_err());
+    }
+}
@@ -0,0 +1,386 @@
+//! Iterators over the elements of quadboards.
+
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::{Range, RangeInclusive};
+
+use halfling::Nibble;
+
+use crate::index::Index;
+use crate::raw_quadboard::RawQuadboard;
+use crate::Quadboard;
+
+/// An iterator over the `(Index, T)` pairs of a [`Quadboard`], in order of
+/// increasing [`Index`].
+///
+/// This is created by [`Quadboard::iter`], or by the [`IntoIterator`] impls
+/// on [`Quadboard`]. Since quadboards are [`Copy`], the iterator holds its own
+/// copy of the board rather than borrowing it.
+#[derive(Debug, Clone)]
+pub struct Iter<T> {
+    board: RawQuadboard,
+    front: u8,
+    back: u8,
+    _data: PhantomData<T>,
+}
+
+impl<T> Iter<T> {
+    /// Creates a new [`Iter`] over all the elements of `board`.
+    pub(crate) const fn new(board: RawQuadboard) -> Self {
+        Self {
+            board,
+            front: 0,
+            back: 64,
+            _data: PhantomData,
+        }
+    }
+
+    /// Decodes the element at `index`.
+    ///
+    /// # Safety
+    /// `index` must be strictly less than 64.
+    unsafe fn item(&self, index: u8) -> (Index, T)
+    where
+        Nibble: Into<T>,
+    {
+        let nibble = unsafe { self.board.get_unchecked(index) };
+        (unsafe { Index::new_unchecked(index) }, nibble.into())
+    }
+}
+
+impl<T> Iterator for Iter<T>
+where
+    Nibble: Into<T>,
+{
+    type Item = (Index, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        let item = unsafe { self.item(self.front) };
+        self.front += 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front) as usize;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<T>
+where
+    Nibble: Into<T>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(unsafe { self.item(self.back) })
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<T> where Nibble: Into<T> {}
+
+impl<T> FusedIterator for Iter<T> where Nibble: Into<T> {}
+
+impl<T> IntoIterator for Quadboard<T>
+where
+    Nibble: Into<T>,
+{
+    type Item = (Index, T);
+    type IntoIter = Iter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(self.inner)
+    }
+}
+
+impl<T> IntoIterator for &Quadboard<T>
+where
+    Nibble: Into<T>,
+{
+    type Item = (Index, T);
+    type IntoIter = Iter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(self.inner)
+    }
+}
+
+/// An iterator over the `(Index, T)` pairs of the nonempty squares of a
+/// [`Quadboard`], in order of increasing [`Index`].
+///
+/// This is created by [`Quadboard::iter_nonempty`]. The empty squares are
+/// skipped using the board's occupancy mask, so each step costs a single
+/// trailing-zero count no matter how many empty squares lie in between.
+#[derive(Debug, Clone)]
+pub struct IterNonempty<T> {
+    board: RawQuadboard,
+    indices: Indices,
+    _data: PhantomData<T>,
+}
+
+impl<T> IterNonempty<T> {
+    /// Creates a new [`IterNonempty`] over the nonempty elements of `board`.
+    pub(crate) const fn new(board: RawQuadboard) -> Self {
+        Self {
+            board,
+            indices: Indices::new(board.occupancy()),
+            _data: PhantomData,
+        }
+    }
+
+    /// Decodes the element at `index`.
+    fn item(&self, index: Index) -> (Index, T)
+    where
+        Nibble: Into<T>,
+    {
+        (index, self.board.get(index).into())
+    }
+}
+
+impl<T> Iterator for IterNonempty<T>
+where
+    Nibble: Into<T>,
+{
+    type Item = (Index, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.indices.next().map(|index| self.item(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IterNonempty<T>
+where
+    Nibble: Into<T>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.indices.next_back().map(|index| self.item(index))
+    }
+}
+
+impl<T> ExactSizeIterator for IterNonempty<T> where Nibble: Into<T> {}
+
+impl<T> FusedIterator for IterNonempty<T> where Nibble: Into<T> {}
+
+/// An iterator over the indices of the set bits in a bitmask, in order of
+/// increasing [`Index`].
+///
+/// Each step costs only a trailing-zero count, so iterating a sparse mask
+/// is much cheaper than testing all 64 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Indices {
+    mask: u64,
+}
+
+impl Indices {
+    /// Creates a new [`Indices`] over the set bits of `mask`.
+    pub const fn new(mask: u64) -> Self {
+        Self { mask }
+    }
+
+    /// Returns the mask of the indices which have not yet been yielded.
+    pub const fn remaining(&self) -> u64 {
+        self.mask
+    }
+}
+
+impl Iterator for Indices {
+    type Item = Index;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.mask {
+            0 => None,
+            mask => {
+                self.mask &= mask - 1;
+                Some(unsafe { Index::new_unchecked(mask.trailing_zeros() as u8) })
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.mask.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Indices {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self.mask {
+            0 => None,
+            mask => {
+                let index = 63 - mask.leading_zeros() as u8;
+                self.mask ^= 1 << index;
+                Some(unsafe { Index::new_unchecked(index) })
+            }
+        }
+    }
+}
+
+impl ExactSizeIterator for Indices {}
+
+impl FusedIterator for Indices {}
+
+/// An iterator over a contiguous range of indices, in order of increasing
+/// [`Index`].
+///
+/// This is created by [`Index::range`], or from a [`Range`] or
+/// [`RangeInclusive`] of indices, and is the stable equivalent of iterating
+/// over an `Index..Index` range directly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexRange {
+    front: u8,
+    back: u8,
+}
+
+impl IndexRange {
+    /// Creates a new [`IndexRange`] from `start` (inclusive) to `end`
+    /// (exclusive), which is empty if `end` is not greater than `start`.
+    pub const fn new(start: Index, end: Index) -> Self {
+        let (front, back) = (start.get(), end.get());
+
+        match front < back {
+            true => Self { front, back },
+            false => Self { front, back: front },
+        }
+    }
+
+    /// Creates a new [`IndexRange`] from `start` to `end`, both inclusive,
+    /// which is empty if `end` is less than `start`.
+    pub const fn new_inclusive(start: Index, end: Index) -> Self {
+        let (front, back) = (start.get(), end.get() + 1);
+
+        match front < back {
+            true => Self { front, back },
+            false => Self { front, back: front },
+        }
+    }
+
+    /// Returns `true` if `index` is yet to be yielded by `self`.
+    pub const fn contains(&self, index: Index) -> bool {
+        self.front <= index.get() && index.get() < self.back
+    }
+}
+
+impl From<Range<Index>> for IndexRange {
+    fn from(value: Range<Index>) -> Self {
+        Self::new(value.start, value.end)
+    }
+}
+
+impl From<RangeInclusive<Index>> for IndexRange {
+    fn from(value: RangeInclusive<Index>) -> Self {
+        Self::new_inclusive(*value.start(), *value.end())
+    }
+}
+
+impl Iterator for IndexRange {
+    type Item = Index;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        let index = unsafe { Index::new_unchecked(self.front) };
+        self.front += 1;
+        Some(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front) as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for IndexRange {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(unsafe { Index::new_unchecked(self.back) })
+    }
+}
+
+impl ExactSizeIterator for IndexRange {}
+
+impl FusedIterator for IndexRange {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iter_visits_every_index_in_order() {
+        let qb = Quadboard::from(std::array::from_fn::<Nibble, 64, _>(|i| {
+            Nibble::try_from((i % 16) as u8).unwrap()
+        }));
+
+        let mut iter = qb.iter();
+        assert_eq!(iter.len(), 64);
+        assert_eq!(iter.next(), Some((Index::MIN, Nibble::ZERO)));
+        assert_eq!(iter.next_back(), Some((Index::MAX, Nibble::FIFTEEN)));
+        assert_eq!(iter.len(), 62);
+
+        for (index, value) in &qb {
+            assert_eq!(value.get(), index.get() % 16);
+        }
+
+        assert_eq!(qb.into_iter().rev().count(), 64);
+    }
+
+    #[test]
+    fn iter_nonempty_skips_empty_squares() {
+        let mut qb = Quadboard::<Nibble>::new_zeroed();
+        qb.set(Index::try_from(3).unwrap(), Nibble::SEVEN);
+        qb.set(Index::try_from(40).unwrap(), Nibble::ONE);
+        qb.set(Index::MAX, Nibble::TWO);
+
+        let mut iter = qb.iter_nonempty();
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some((Index::MAX, Nibble::TWO)));
+        assert_eq!(
+            iter.map(|(index, value)| (index.get(), value.get()))
+                .collect::<Vec<_>>(),
+            [(3, 7), (40, 1)]
+        );
+        assert_eq!(Quadboard::<Nibble>::new_zeroed().iter_nonempty().count(), 0);
+    }
+
+    #[test]
+    fn indices_walk_set_bits_from_both_ends() {
+        let mut indices = Indices::new((1 << 63) | (1 << 10) | 1);
+        assert_eq!(indices.len(), 3);
+        assert_eq!(indices.next(), Some(Index::MIN));
+        assert_eq!(indices.next_back(), Some(Index::MAX));
+        assert_eq!(indices.next().map(|index| index.get()), Some(10));
+        assert_eq!(indices.next(), None);
+    }
+
+    #[test]
+    fn index_ranges_are_half_open_or_inclusive() {
+        let (a, b) = (Index::try_from(10).unwrap(), Index::try_from(14).unwrap());
+
+        assert!(IndexRange::new(a, b).map(|index| index.get()).eq(10..14));
+        assert!(IndexRange::from(a..=b)
+            .rev()
+            .map(|index| index.get())
+            .eq((10..=14).rev()));
+        assert_eq!(IndexRange::from(Index::MIN..=Index::MAX).len(), 64);
+        assert_eq!(IndexRange::new(b, a).len(), 0);
+        assert_eq!(IndexRange::new_inclusive(a, a).len(), 1);
+        assert!(IndexRange::new(a, b).contains(a));
+        assert!(!IndexRange::new(a, b).contains(b));
+    }
+}
@@ -0,0 +1,128 @@
+//! The four-lane vector of `u64` channels backing a
+//! [`RawQuadboard`](crate::raw_quadboard::RawQuadboard).
+//!
+//! With the `simd` feature enabled this is just `core::simd::u64x4`; otherwise
+//! it is a scalar stand-in implementing the (small) subset of its API used by
+//! this crate, which allows the crate to build on stable Rust.
+
+#[cfg(feature = "simd")]
+pub(crate) use core::simd::u64x4;
+
+#[cfg(not(feature = "simd"))]
+pub(crate) use scalar::u64x4;
+
+#[cfg(not(feature = "simd"))]
+mod scalar {
+    use core::ops::{
+        BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Mul, Not, Shl, Shr,
+    };
+
+    /// A scalar equivalent to `core::simd::u64x4`.
+    #[allow(non_camel_case_types)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+    #[repr(C, align(32))]
+    pub(crate) struct u64x4([u64; 4]);
+
+    impl u64x4 {
+        /// Constructs a vector from an array of lanes.
+        #[inline(always)]
+        pub(crate) const fn from_array(array: [u64; 4]) -> Self {
+            Self(array)
+        }
+
+        /// Constructs a vector with every lane set to `value`.
+        #[inline(always)]
+        pub(crate) const fn splat(value: u64) -> Self {
+            Self([value; 4])
+        }
+
+        /// Returns the lanes of `self` as an array.
+        #[inline(always)]
+        pub(crate) const fn to_array(self) -> [u64; 4] {
+            self.0
+        }
+
+        /// Returns a reference to the lanes of `self`.
+        #[inline(always)]
+        pub(crate) const fn as_array(&self) -> &[u64; 4] {
+            &self.0
+        }
+
+        /// Returns a mutable reference to the lanes of `self`.
+        #[inline(always)]
+        pub(crate) fn as_mut_array(&mut self) -> &mut [u64; 4] {
+            &mut self.0
+        }
+
+        /// Applies `f` to each pair of corresponding lanes.
+        #[inline(always)]
+        fn zip_with(self, rhs: Self, f: impl Fn(u64, u64) -> u64) -> Self {
+            let [a0, a1, a2, a3] = self.0;
+            let [b0, b1, b2, b3] = rhs.0;
+            Self([f(a0, b0), f(a1, b1), f(a2, b2), f(a3, b3)])
+        }
+    }
+
+    /// Generates lanewise binary operator impls, along with their
+    /// assigning variants where given.
+    macro_rules! lanewise_ops {
+        ($($trait:ident::$method:ident $(/ $assign:ident::$assign_method:ident)? => $f:expr),+ $(,)?) => {
+            $(
+                impl $trait for u64x4 {
+                    type Output = Self;
+
+                    #[inline(always)]
+                    fn $method(self, rhs: Self) -> Self::Output {
+                        self.zip_with(rhs, $f)
+                    }
+                }
+
+                $(
+                    impl $assign for u64x4 {
+                        #[inline(always)]
+                        fn $assign_method(&mut self, rhs: Self) {
+                            *self = $trait::$method(*self, rhs);
+                        }
+                    }
+                )?
+            )+
+        };
+    }
+
+    lanewise_ops!(
+        BitAnd::bitand / BitAndAssign::bitand_assign => |a, b| a & b,
+        BitOr::bitor / BitOrAssign::bitor_assign => |a, b| a | b,
+        BitXor::bitxor / BitXorAssign::bitxor_assign => |a, b| a ^ b,
+        Mul::mul => u64::wrapping_mul,
+        Shl::shl => |a, b| a.wrapping_shl(b as u32),
+        Shr::shr => |a, b| a.wrapping_shr(b as u32),
+    );
+
+    impl Shl<u64> for u64x4 {
+        type Output = Self;
+
+        #[inline(always)]
+        fn shl(self, rhs: u64) -> Self::Output {
+            self << Self::splat(rhs)
+        }
+    }
+
+    impl Shr<u64> for u64x4 {
+        type Output = Self;
+
+        #[inline(always)]
+        fn shr(self, rhs: u64) -> Self::Output {
+            self >> Self::splat(rhs)
+        }
+    }
+
+    impl Not for u64x4 {
+        type Output = Self;
+
+        #[inline(always)]
+        fn not(self) -> Self::Output {
+            let [a0, a1, a2, a3] = self.0;
+            Self([!a0, !a1, !a2, !a3])
+        }
+    }
+}
@@ -19,23 +19,164 @@
 //! [`Into`], [`TryFrom`], and [`TryInto`] impls whose type parameter is [`Nibble`].
 //!
 //! # SIMD
-//! As the `portable_simd` feature is currently nightly-only, this crate is also
-//! considered to be unstable. It's possible to write non-SIMD equivalents to the
-//! algorithms used here and provide the SIMD versions via cargo feature flags,
-//! but my hope is that [std::simd] will be stabilised relatively soon.
+//! By default, the channels of a quadboard are held in a [`core::simd`] vector,
+//! which requires the nightly-only `portable_simd` feature. My hope is that
+//! [`core::simd`] will be stabilised relatively soon, but in the meantime the
+//! default `simd` cargo feature can be disabled to build this crate on stable
+//! Rust, in which case the channels are instead manipulated with scalar
+//! instructions behind the same API.
+//!
+//! # `no_std`
+//! This crate is `no_std` unless the default `std` feature is enabled, which
+//! provides [`std::error::Error`] impls for the error types along with the
+//! [`io`] and [`dataset`] modules (and is required by the `interner` and `png`
+//! features). The [`stack`] module only needs an allocator, and is available
+//! under the `alloc` feature, which `std` implies. Note that the [`Nibble`] type is provided by `halfling`, which
+//! itself still links against `std`, so for now this only removes this crate's
+//! own dependence on `std` and does not make it usable on `no_std` targets.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![warn(missing_docs)]
-#![feature(portable_simd)]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+// allows the code generated by the derive macros to refer to this crate
+// by name, including from within it
+#[cfg(feature = "derive")]
+extern crate self as quadboard;
+
+pub mod blocks;
+#[cfg(feature = "board-file")]
+pub mod board_file;
+pub mod checksum;
+pub mod codec;
+pub mod counter;
+pub mod direction;
+pub mod display;
+pub mod entry;
 pub mod index;
+pub mod iter;
+mod lanes;
+pub mod multiboard;
+pub mod overlay;
+pub mod prelude;
+pub mod quadboard81;
 pub mod raw_quadboard;
 
-use std::marker::PhantomData;
+#[cfg(target_has_atomic = "64")]
+pub mod atomic;
+#[cfg(feature = "chess")]
+pub mod chess;
+#[cfg(feature = "std")]
+pub mod dataset;
+#[cfg(feature = "draughts")]
+pub mod draughts;
+#[cfg(feature = "interner")]
+pub mod interner;
+#[cfg(feature = "std")]
+pub mod io;
+#[cfg(feature = "png")]
+pub mod render;
+#[cfg(feature = "reversi")]
+pub mod reversi;
+#[cfg(feature = "alloc")]
+pub mod stack;
+#[cfg(feature = "zobrist")]
+pub mod zobrist;
+
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
 
-use crate::index::Index;
+use crate::codec::EmptySquare;
+use crate::direction::Direction;
+use crate::display::DisplayWith;
+use crate::entry::Entry;
+use crate::index::{File, Index, Rank};
 use crate::raw_quadboard::RawQuadboard;
 pub use halfling::Nibble;
 
+/// The error produced when pushing a value onto a full column.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ColumnFullError(pub File);
+
+impl core::fmt::Display for ColumnFullError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Attempted to push onto the full column {:?}.", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ColumnFullError {}
+
+/// The error produced when a [`RawQuadboard`] holds nibbles which do not
+/// encode a value of the target type, recording the mask of their indices.
+///
+/// The mask is never zero, so there is always a [first](InvalidBoardError::first)
+/// invalid index.
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvalidBoardError(u64);
+
+impl InvalidBoardError {
+    /// Constructs an [`InvalidBoardError`] from the mask of invalid indices,
+    /// or returns `None` if `mask` is zero.
+    pub(crate) const fn new(mask: u64) -> Option<Self> {
+        match mask {
+            0 => None,
+            mask => Some(Self(mask)),
+        }
+    }
+
+    /// Returns the mask of the indices holding invalid nibbles.
+    pub const fn mask(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns the least index holding an invalid nibble.
+    pub const fn first(&self) -> Index {
+        // the mask is nonzero, so this is at most 63
+        unsafe { Index::new_unchecked(self.0.trailing_zeros() as u8) }
+    }
+}
+
+impl core::fmt::Display for InvalidBoardError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "The board holds {} invalid nibbles, the first at {:?}.",
+            self.0.count_ones(),
+            self.first()
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidBoardError {}
+
+/// The error produced by [`Quadboard::try_map`], recording the first
+/// [`Index`] at which the mapping failed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct MapError<E> {
+    /// The index of the element which could not be mapped.
+    pub index: Index,
+    /// The error produced when mapping the element.
+    pub error: E,
+}
+
+impl<E: core::fmt::Debug> core::fmt::Display for MapError<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Attempted to map the element at {:?}, which failed with {:?}.",
+            self.index, self.error
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: core::fmt::Debug> std::error::Error for MapError<E> {}
+
 /// A fixed-length 32-byte buffer of 64 `T` values.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Quadboard<T> {
@@ -43,6 +184,14 @@ pub struct Quadboard<T> {
     _data: PhantomData<T>,
 }
 
+// implemented manually to avoid requiring `T: Hash`, since only the
+// underlying nibbles are hashed
+impl<T> Hash for Quadboard<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner.hash(state);
+    }
+}
+
 impl<T> Default for Quadboard<T>
 where
     T: Default + Into<Nibble>,
@@ -55,7 +204,144 @@ where
     }
 }
 
+/// Quadboards are ordered lexicographically by their decoded values, from
+/// [`Index::MIN`] to [`Index::MAX`].
+///
+/// Since distinct nibbles may decode to equal `T` values, boards whose decoded
+/// values are all equal are further ordered lexicographically by their
+/// underlying nibbles; this keeps the ordering consistent with [`Eq`].
+impl<T> Ord for Quadboard<T>
+where
+    T: Ord,
+    Nibble: Into<T>,
+{
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        let decoded =
+            (0..64u8).map(|i| unsafe { self.get_unchecked(i).cmp(&other.get_unchecked(i)) });
+        let raw = (0..64u8).map(|i| unsafe {
+            self.inner
+                .get_unchecked(i)
+                .cmp(&other.inner.get_unchecked(i))
+        });
+
+        decoded
+            .chain(raw)
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(core::cmp::Ordering::Equal)
+    }
+}
+
+impl<T> PartialOrd for Quadboard<T>
+where
+    T: Ord,
+    Nibble: Into<T>,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> From<[T; 64]> for Quadboard<T>
+where
+    T: Into<Nibble>,
+{
+    fn from(value: [T; 64]) -> Self {
+        let bytes = value.map(|element| element.into().get());
+
+        Self {
+            inner: unsafe { RawQuadboard::from_nibble_bytes(bytes) },
+            _data: PhantomData,
+        }
+    }
+}
+
+impl<T> TryFrom<RawQuadboard> for Quadboard<T>
+where
+    T: TryFrom<Nibble>,
+{
+    type Error = InvalidBoardError;
+
+    fn try_from(value: RawQuadboard) -> Result<Self, Self::Error> {
+        Self::try_from_raw(value)
+    }
+}
+
+/// Collects exactly 64 values into a [`Quadboard`], in order of increasing
+/// [`Index`].
+///
+/// # Panics
+/// Panics if the iterator yields fewer or more than 64 values.
+impl<T> FromIterator<T> for Quadboard<T>
+where
+    T: Into<Nibble>,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut iter = iter.into_iter();
+        let mut bytes = [0u8; 64];
+
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            match iter.next() {
+                Some(value) => *byte = value.into().get(),
+                None => panic!("Attempted to collect a Quadboard from only {i} values."),
+            }
+        }
+
+        assert!(
+            iter.next().is_none(),
+            "Attempted to collect a Quadboard from more than 64 values."
+        );
+
+        Self {
+            inner: unsafe { RawQuadboard::from_nibble_bytes(bytes) },
+            _data: PhantomData,
+        }
+    }
+}
+
 impl<T> Quadboard<T> {
+    /// Creates a new [`Quadboard`] whose element at each [`Index`] is `f(index)`,
+    /// calling `f` in order of increasing [`Index`].
+    pub fn from_fn(mut f: impl FnMut(Index) -> T) -> Self
+    where
+        T: Into<Nibble>,
+    {
+        let bytes =
+            core::array::from_fn(|i| f(unsafe { Index::new_unchecked(i as u8) }).into().get());
+
+        Self {
+            inner: unsafe { RawQuadboard::from_nibble_bytes(bytes) },
+            _data: PhantomData,
+        }
+    }
+
+    /// Creates a new [`Quadboard`] whose element at each [`Index`] is drawn
+    /// from `rng` by `f`, calling `f` in order of increasing [`Index`].
+    ///
+    /// This places no requirements on `R`, so any random number generator
+    /// can be used; with `rand`, for example, `f` might be
+    /// `|_, rng| rng.gen()`.
+    pub fn random_with<R>(rng: &mut R, mut f: impl FnMut(Index, &mut R) -> T) -> Self
+    where
+        T: Into<Nibble>,
+    {
+        Self::from_fn(|index| f(index, rng))
+    }
+
+    /// Creates a new [`Quadboard`] with every element set to the zero nibble.
+    ///
+    /// Unlike [`Quadboard::default`], this requires only that the zero
+    /// nibble is a valid encoding of `T`, as declared by [`EmptySquare`].
+    #[inline(always)]
+    pub const fn new_zeroed() -> Self
+    where
+        T: EmptySquare,
+    {
+        Self {
+            inner: RawQuadboard::from_channels([0; 4]),
+            _data: PhantomData,
+        }
+    }
+
     /// Returns the value at the given [`Index`].
     #[inline(always)]
     pub fn get(&self, index: Index) -> T
@@ -79,6 +365,62 @@ impl<T> Quadboard<T> {
         nibble.into()
     }
 
+    /// Returns an adaptor which displays `self` as an 8×8 grid, rendering
+    /// each element with `render`.
+    ///
+    /// See [`DisplayWith`] for details of the format.
+    pub fn display_with<F, D>(&self, render: F) -> DisplayWith<'_, T, F>
+    where
+        F: Fn(T) -> D,
+        D: core::fmt::Display,
+    {
+        DisplayWith::new(self, render)
+    }
+
+    /// Returns an [`Entry`] for in-place reads and writes of the element at
+    /// `index`.
+    #[inline(always)]
+    pub fn entry(&mut self, index: Index) -> Entry<'_, T> {
+        Entry::new(self, index)
+    }
+
+    /// Returns the values at each of `indices`, in the same order.
+    ///
+    /// See [`RawQuadboard::get_many`] for details.
+    #[inline(always)]
+    pub fn get_many<const N: usize>(&self, indices: [Index; N]) -> [T; N]
+    where
+        Nibble: Into<T>,
+    {
+        self.inner.get_many(indices).map(Into::into)
+    }
+
+    /// Attempts to convert the [`Nibble`] at the given [`Index`] into a `T`.
+    ///
+    /// This is the fallible equivalent of [`Quadboard::get`], for encodings
+    /// in which some nibbles do not correspond to a `T` value.
+    #[inline(always)]
+    pub fn try_get(&self, index: Index) -> Result<T, T::Error>
+    where
+        T: TryFrom<Nibble>,
+    {
+        unsafe { self.try_get_unchecked(index.get()) }
+    }
+
+    /// Reads the [`Nibble`] at the given index and
+    /// passes it to `T::try_from`.
+    ///
+    /// # Safety
+    /// `index` must be less than 64.
+    #[inline(always)]
+    pub unsafe fn try_get_unchecked(&self, index: u8) -> Result<T, T::Error>
+    where
+        T: TryFrom<Nibble>,
+    {
+        let nibble = unsafe { self.inner.get_unchecked(index) };
+        T::try_from(nibble)
+    }
+
     /// Converts `value` into a [`Nibble`] and writes the
     /// resulting `T` value to the element at `index`.
     #[inline(always)]
@@ -89,6 +431,109 @@ impl<T> Quadboard<T> {
         unsafe { self.set_unchecked(index.get(), value) };
     }
 
+    /// Overwrites every element of `self` with `value`.
+    #[inline(always)]
+    pub fn fill(&mut self, value: T)
+    where
+        T: Into<Nibble>,
+    {
+        self.inner = RawQuadboard::splat(value.into());
+    }
+
+    /// Overwrites every element of `self` with `T::default()`.
+    #[inline(always)]
+    pub fn clear(&mut self)
+    where
+        T: Default + Into<Nibble>,
+    {
+        self.fill(T::default());
+    }
+
+    /// Writes `value` to every index set in `mask`, leaving the other
+    /// elements unchanged.
+    #[inline(always)]
+    pub fn fill_masked(&mut self, mask: u64, value: T)
+    where
+        T: Into<Nibble>,
+    {
+        self.inner.fill_masked(mask, value.into());
+    }
+
+    /// Returns a new board taking the elements of `other` at the indices set
+    /// in `mask`, and the elements of `self` everywhere else.
+    #[inline(always)]
+    pub fn blend(&self, mask: u64, other: &Self) -> Self {
+        Self::from_raw(self.inner.blend(mask, &other.inner))
+    }
+
+    /// Exchanges the elements at `a` and `b`.
+    #[inline(always)]
+    pub fn swap(&mut self, a: Index, b: Index) {
+        self.inner.swap(a, b);
+    }
+
+    /// Converts and applies each of `writes` in order, so that later writes
+    /// to an index take precedence over earlier ones.
+    ///
+    /// See [`RawQuadboard::set_many`] for details.
+    #[inline(always)]
+    pub fn set_many(&mut self, writes: &[(Index, T)])
+    where
+        T: Copy + Into<Nibble>,
+    {
+        self.inner
+            .set_all(writes.iter().map(|&(index, value)| (index, value.into())));
+    }
+
+    /// Returns the eight elements in `rank`, in order of increasing file.
+    #[inline(always)]
+    pub fn rank(&self, rank: Rank) -> [T; 8]
+    where
+        Nibble: Into<T>,
+    {
+        self.inner.rank(rank).map(Into::into)
+    }
+
+    /// Overwrites the eight elements in `rank` with `values`, in order of
+    /// increasing file.
+    #[inline(always)]
+    pub fn set_rank(&mut self, rank: Rank, values: [T; 8])
+    where
+        T: Into<Nibble>,
+    {
+        self.inner.set_rank(rank, values.map(Into::into));
+    }
+
+    /// Returns the eight elements in `file`, in order of increasing rank.
+    #[inline(always)]
+    pub fn file(&self, file: File) -> [T; 8]
+    where
+        Nibble: Into<T>,
+    {
+        self.inner.file(file).map(Into::into)
+    }
+
+    /// Overwrites the eight elements in `file` with `values`, in order of
+    /// increasing rank.
+    #[inline(always)]
+    pub fn set_file(&mut self, file: File, values: [T; 8])
+    where
+        T: Into<Nibble>,
+    {
+        self.inner.set_file(file, values.map(Into::into));
+    }
+
+    /// Moves the element at `from` to `to`, leaving `fill` in its place.
+    ///
+    /// See [`RawQuadboard::relocate`] for details.
+    #[inline(always)]
+    pub fn relocate(&mut self, from: Index, to: Index, fill: T)
+    where
+        T: Into<Nibble>,
+    {
+        self.inner.relocate(from, to, fill.into());
+    }
+
     /// Converts `value` into a [`Nibble`] and writes the
     /// resulting `T` value to `index` without bounds checking.
     ///
@@ -103,9 +548,821 @@ impl<T> Quadboard<T> {
         unsafe { self.inner.set_unchecked(index, value) };
     }
 
+    /// Returns a bitmask with a bit set at every index whose element is
+    /// encoded by the same [`Nibble`] as `value`.
+    ///
+    /// See [`RawQuadboard::eq_mask`] for details.
+    #[inline(always)]
+    pub fn mask_of(&self, value: T) -> u64
+    where
+        T: Into<Nibble>,
+    {
+        self.inner.eq_mask(value.into())
+    }
+
+    /// Returns a bitmask with a bit set at every index whose element differs
+    /// between `self` and `other`.
+    #[inline(always)]
+    pub fn diff_mask(&self, other: &Self) -> u64 {
+        self.inner.diff_mask(&other.inner)
+    }
+
+    /// Returns a bitmask with a bit set at every index whose encoded element
+    /// satisfies `predicate`.
+    ///
+    /// The predicate is evaluated once for each of the sixteen nibbles, and
+    /// the mask is then assembled from the channels, so this costs the same
+    /// however many elements match.
+    pub fn mask_where(&self, predicate: impl Fn(Nibble) -> bool) -> u64 {
+        let values = (0..16u8).fold(0u16, |values, value| {
+            match predicate(unsafe { Nibble::new_unchecked(value) }) {
+                true => values | (1 << value),
+                false => values,
+            }
+        });
+
+        self.inner.values_mask(values)
+    }
+
+    /// Returns the number of elements of `self` encoded by the same
+    /// [`Nibble`] as `value`.
+    ///
+    /// This is a single popcount over the mask given by [`Quadboard::mask_of`].
+    #[inline(always)]
+    pub fn count_of(&self, value: T) -> u32
+    where
+        T: Into<Nibble>,
+    {
+        self.mask_of(value).count_ones()
+    }
+
+    /// Returns `true` if any element of `self` is encoded by the same
+    /// [`Nibble`] as `value`.
+    #[inline(always)]
+    pub fn contains(&self, value: T) -> bool
+    where
+        T: Into<Nibble>,
+    {
+        self.mask_of(value) != 0
+    }
+
+    /// Returns `true` if any element of `self` satisfies `predicate`.
+    ///
+    /// The predicate is evaluated at most once for each distinct element,
+    /// rather than once for each square.
+    pub fn any(&self, predicate: impl Fn(T) -> bool) -> bool
+    where
+        Nibble: Into<T>,
+    {
+        let present = self.inner.present_values();
+
+        (0..16u8)
+            .filter(|value| present & (1 << value) != 0)
+            .any(|value| predicate(unsafe { Nibble::new_unchecked(value) }.into()))
+    }
+
+    /// Returns `true` if every element of `self` satisfies `predicate`.
+    ///
+    /// The predicate is evaluated at most once for each distinct element,
+    /// rather than once for each square.
+    pub fn all(&self, predicate: impl Fn(T) -> bool) -> bool
+    where
+        Nibble: Into<T>,
+    {
+        !self.any(|value| !predicate(value))
+    }
+
+    /// Returns the least [`Index`] whose element is encoded by the same
+    /// [`Nibble`] as `value`, if any.
+    #[inline(always)]
+    pub fn first_index_of(&self, value: T) -> Option<Index>
+    where
+        T: Into<Nibble>,
+    {
+        self.positions_of(value).next()
+    }
+
+    /// Returns an iterator over the indices whose elements are encoded by
+    /// the same [`Nibble`] as `value`, in order of increasing [`Index`].
+    #[inline(always)]
+    pub fn positions_of(&self, value: T) -> iter::Indices
+    where
+        T: Into<Nibble>,
+    {
+        iter::Indices::new(self.mask_of(value))
+    }
+
+    /// Writes `new` to the element at `index` if and only if it currently
+    /// holds `expected`, returning whether the write took place.
+    ///
+    /// The comparison is done on the encoded [`Nibble`] values, so `T`
+    /// need not implement [`PartialEq`].
+    #[inline(always)]
+    pub fn set_if(&mut self, index: Index, expected: T, new: T) -> bool
+    where
+        T: Into<Nibble>,
+    {
+        let index = index.get();
+        let expected: Nibble = expected.into();
+        let matches = unsafe { self.inner.get_unchecked(index) } == expected;
+
+        if matches {
+            unsafe { self.inner.set_unchecked(index, new.into()) };
+        }
+
+        matches
+    }
+
+    /// Writes `value` to every element for which `f` returns `true`, and
+    /// returns a bitmask of the indices that were written to.
+    ///
+    /// The predicate is evaluated once per element, after which all the
+    /// writes are applied in a single masked update. If the predicate only
+    /// depends on the encoded value of each element, then
+    /// [`Quadboard::replace_nibbles`] is considerably faster.
+    pub fn replace_where(&mut self, mut f: impl FnMut(Index, T) -> bool, value: T) -> u64
+    where
+        T: Into<Nibble>,
+        Nibble: Into<T>,
+    {
+        let mask = (0..64u8).fold(0u64, |acc, i| {
+            let index = unsafe { Index::new_unchecked(i) };
+            match f(index, self.get(index)) {
+                true => acc | (1 << i),
+                false => acc,
+            }
+        });
+
+        self.inner.fill_masked(mask, value.into());
+        mask
+    }
+
+    /// Rewrites every element encoded by the same [`Nibble`] as `old` to
+    /// `new`, and returns a bitmask of the indices that were rewritten.
+    ///
+    /// See [`RawQuadboard::replace_all`] for details.
+    #[inline(always)]
+    pub fn replace_all(&mut self, old: T, new: T) -> u64
+    where
+        T: Into<Nibble>,
+    {
+        self.inner.replace_all(old.into(), new.into())
+    }
+
+    /// Writes `value` to every element whose encoding is in `values`, where
+    /// bit `n` of `values` stands for the nibble `n`, and returns a bitmask
+    /// of the indices that were written to.
+    ///
+    /// This is computed directly from the channels, without decoding any of
+    /// the elements of `self`.
+    pub fn replace_nibbles(&mut self, values: u16, value: T) -> u64
+    where
+        T: Into<Nibble>,
+    {
+        let mask = self.inner.values_mask(values);
+        self.inner.fill_masked(mask, value.into());
+        mask
+    }
+
+    /// Attempts to convert `value` into a [`Nibble`], writing it to
+    /// the element at `index` if successful.
+    ///
+    /// If the conversion fails, `self` is left unchanged and the conversion
+    /// error is returned.
+    #[inline(always)]
+    pub fn checked_set(&mut self, index: Index, value: T) -> Result<(), T::Error>
+    where
+        T: TryInto<Nibble>,
+    {
+        let value: Nibble = value.try_into()?;
+        unsafe { self.inner.set_unchecked(index.get(), value) };
+        Ok(())
+    }
+
+    /// Writes `value` to the lowest empty square of `file`, as in a game
+    /// where pieces are dropped into columns, and returns its index.
+    ///
+    /// A square is considered empty if it holds the zero nibble, and the
+    /// lowest square of a file is the one with the least index.
+    pub fn push_column(&mut self, file: File, value: T) -> Result<Index, ColumnFullError>
+    where
+        T: Into<Nibble> + EmptySquare,
+    {
+        let empty = !self.inner.occupancy() & file.mask();
+
+        match empty {
+            0 => Err(ColumnFullError(file)),
+            _ => {
+                let index = unsafe { Index::new_unchecked(empty.trailing_zeros() as u8) };
+                self.set(index, value);
+                Ok(index)
+            }
+        }
+    }
+
+    /// Removes the value from the highest nonempty square of `file`, leaving
+    /// the zero nibble in its place, and returns it along with its index.
+    pub fn pop_column(&mut self, file: File) -> Option<(Index, T)>
+    where
+        T: EmptySquare,
+        Nibble: Into<T>,
+    {
+        let occupied = self.inner.occupancy() & file.mask();
+
+        match occupied {
+            0 => None,
+            _ => {
+                let index = unsafe { Index::new_unchecked(63 - occupied.leading_zeros() as u8) };
+                let value = self.get(index);
+                self.inner.set(index, Nibble::ZERO);
+                Some((index, value))
+            }
+        }
+    }
+
+    /// Returns an iterator over the `(Index, T)` pairs of `self`, in order
+    /// of increasing [`Index`].
+    #[inline(always)]
+    pub fn iter(&self) -> iter::Iter<T>
+    where
+        Nibble: Into<T>,
+    {
+        iter::Iter::new(self.inner)
+    }
+
+    /// Returns an iterator over the `(Index, T)` pairs of the nonempty
+    /// squares of `self`, in order of increasing [`Index`].
+    ///
+    /// A square is considered empty if it holds the zero nibble, which
+    /// [`EmptySquare`] guarantees is a valid encoding of `T`. Only the
+    /// nonempty squares are visited, so this is much cheaper than filtering
+    /// [`Quadboard::iter`] on sparse boards.
+    #[inline(always)]
+    pub fn iter_nonempty(&self) -> iter::IterNonempty<T>
+    where
+        T: EmptySquare,
+        Nibble: Into<T>,
+    {
+        iter::IterNonempty::new(self.inner)
+    }
+
+    /// Returns an iterator over the runs of consecutive squares holding
+    /// the same value, in increasing [`Index`] order.
+    ///
+    /// Each item is the value of a run together with its length, so the
+    /// lengths of all the runs always sum to 64. Squares are compared by
+    /// their underlying [`Nibble`], so distinct encodings will always form
+    /// distinct runs even if they decode to equal `T` values.
+    pub fn runs(&self) -> impl Iterator<Item = (T, u8)> + '_
+    where
+        Nibble: Into<T>,
+    {
+        // a bit is set in `boundaries` wherever a square differs from its
+        // predecessor in at least one channel; the first square always
+        // starts a new run
+        let mut boundaries = self
+            .inner
+            .as_channels()
+            .iter()
+            .fold(1u64, |acc, channel| acc | (channel ^ (channel << 1)));
+
+        core::iter::from_fn(move || {
+            if boundaries == 0 {
+                return None;
+            }
+
+            let start = boundaries.trailing_zeros() as u8;
+            boundaries &= boundaries - 1;
+            let end = match boundaries {
+                0 => 64,
+                rest => rest.trailing_zeros() as u8,
+            };
+
+            let value = unsafe { self.get_unchecked(start) };
+            Some((value, end - start))
+        })
+    }
+
+    /// Decodes every element of `self`, returning them as an array
+    /// ordered by [`Index`].
+    ///
+    /// This is the inverse of the `From<[T; 64]>` impl.
+    pub fn into_array(self) -> [T; 64]
+    where
+        Nibble: Into<T>,
+    {
+        self.inner
+            .to_nibble_bytes()
+            .map(|byte| unsafe { Nibble::new_unchecked(byte) }.into())
+    }
+
+    /// Applies `f` to every element of `self` in order of increasing
+    /// [`Index`], producing a board of the results.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Quadboard<U>
+    where
+        Nibble: Into<T>,
+        U: Into<Nibble>,
+    {
+        Quadboard::from(self.into_array().map(f))
+    }
+
+    /// Replaces every element of `self` with the result of calling `f` on
+    /// its index and value, in order of increasing [`Index`].
+    ///
+    /// If the new value only depends on the encoding of the old one, then
+    /// [`RawQuadboard::translate`] computes the same result from the channels.
+    pub fn map_in_place(&mut self, mut f: impl FnMut(Index, T) -> T)
+    where
+        T: Into<Nibble>,
+        Nibble: Into<T>,
+    {
+        let mut bytes = self.inner.to_nibble_bytes();
+
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            let index = unsafe { Index::new_unchecked(i as u8) };
+            let value = unsafe { Nibble::new_unchecked(*byte) }.into();
+            *byte = f(index, value).into().get();
+        }
+
+        self.inner = unsafe { RawQuadboard::from_nibble_bytes(bytes) };
+    }
+
+    /// Applies the fallible `f` to every element of `self` in order of
+    /// increasing [`Index`], stopping at the first failure.
+    pub fn try_map<U, E>(
+        self,
+        mut f: impl FnMut(T) -> Result<U, E>,
+    ) -> Result<Quadboard<U>, MapError<E>>
+    where
+        Nibble: Into<T>,
+        U: Into<Nibble>,
+    {
+        let mut bytes = self.inner.to_nibble_bytes();
+
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            let value = unsafe { Nibble::new_unchecked(*byte) }.into();
+
+            match f(value) {
+                Ok(value) => *byte = value.into().get(),
+                Err(error) => {
+                    let index = unsafe { Index::new_unchecked(i as u8) };
+                    return Err(MapError { index, error });
+                }
+            }
+        }
+
+        Ok(Quadboard {
+            inner: unsafe { RawQuadboard::from_nibble_bytes(bytes) },
+            _data: PhantomData,
+        })
+    }
+
+    /// Mirrors `self` vertically, exchanging the first and last ranks.
+    ///
+    /// See [`RawQuadboard::flip_vertical`] for details.
+    #[inline(always)]
+    pub fn flip_vertical(&self) -> Self {
+        Self::from_raw(self.inner.flip_vertical())
+    }
+
+    /// Mirrors `self` horizontally, exchanging the first and last files.
+    ///
+    /// See [`RawQuadboard::flip_horizontal`] for details.
+    #[inline(always)]
+    pub fn flip_horizontal(&self) -> Self {
+        Self::from_raw(self.inner.flip_horizontal())
+    }
+
+    /// Rotates `self` by 180 degrees.
+    ///
+    /// See [`RawQuadboard::rotate_180`] for details.
+    #[inline(always)]
+    pub fn rotate_180(&self) -> Self {
+        Self::from_raw(self.inner.rotate_180())
+    }
+
+    /// Mirrors `self` about the diagonal through indices 0 and 63.
+    ///
+    /// See [`RawQuadboard::flip_diagonal`] for details.
+    #[inline(always)]
+    pub fn flip_diagonal(&self) -> Self {
+        Self::from_raw(self.inner.flip_diagonal())
+    }
+
+    /// Moves every element one square in the given direction, writing
+    /// `fill` to the squares left vacated.
+    ///
+    /// See [`RawQuadboard::shift`] for details.
+    #[inline(always)]
+    pub fn shift(&self, direction: Direction, fill: T) -> Self
+    where
+        T: Into<Nibble>,
+    {
+        Self::from_raw(self.inner.shift(direction, fill.into()))
+    }
+
+    /// Wraps `inner` in a typed board without checking its elements.
+    ///
+    /// This is always sound, since every read from a [`Quadboard`] decodes
+    /// its element with `From<Nibble>` or `TryFrom<Nibble>`; it is the usual
+    /// way to build a typed board in a `const` item, as in the following.
+    ///
+    /// ```
+    /// use quadboard::prelude::*;
+    ///
+    /// const BOARD: Quadboard<Nibble> = {
+    ///     let mut raw = RawQuadboard::splat(Nibble::ONE);
+    ///     raw.set(Index::MAX, Nibble::TWO);
+    ///     Quadboard::from_raw(raw)
+    /// };
+    ///
+    /// assert_eq!(BOARD.get(Index::MAX), Nibble::TWO);
+    /// ```
+    #[inline(always)]
+    pub const fn from_raw(inner: RawQuadboard) -> Self {
+        Self {
+            inner,
+            _data: PhantomData,
+        }
+    }
+
+    /// Wraps `inner` in a typed board, checking that each of its elements
+    /// encodes a `T`.
+    ///
+    /// Every element is checked at once against the mask produced by
+    /// [`RawQuadboard::invalid_mask`], so this costs no more than sixteen
+    /// equality masks regardless of the contents of `inner`.
+    #[inline(always)]
+    pub fn try_from_raw(inner: RawQuadboard) -> Result<Self, InvalidBoardError>
+    where
+        T: TryFrom<Nibble>,
+    {
+        match InvalidBoardError::new(inner.invalid_mask::<T>()) {
+            None => Ok(Self::from_raw(inner)),
+            Some(error) => Err(error),
+        }
+    }
+
     /// Returns a reference to the underlying [`RawQuadboard`].
     #[inline(always)]
     pub const fn as_raw_quadboard(&self) -> &RawQuadboard {
         &self.inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A minimal value type used to exercise the typed API.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    struct Cell(u8);
+
+    impl From<Cell> for Nibble {
+        fn from(value: Cell) -> Self {
+            Nibble::try_from(value.0).unwrap()
+        }
+    }
+
+    impl From<Nibble> for Cell {
+        fn from(value: Nibble) -> Self {
+            Cell(value.get())
+        }
+    }
+
+    #[test]
+    fn checked_set_rejects_unencodable_values() {
+        let mut qb = Quadboard::<u8> {
+            inner: RawQuadboard::default(),
+            _data: PhantomData,
+        };
+
+        let index = 5.try_into().unwrap();
+        assert!(qb.checked_set(index, 12).is_ok());
+        assert!(qb.checked_set(index, 16).is_err());
+        assert_eq!(qb.get(index), 12);
+    }
+
+    #[test]
+    fn quadboards_are_ordered_by_decoded_values() {
+        let mut lesser = Quadboard::<Cell>::default();
+        let mut greater = Quadboard::<Cell>::default();
+        lesser.set(3.try_into().unwrap(), Cell(9));
+        greater.set(2.try_into().unwrap(), Cell(1));
+
+        assert!(lesser < greater);
+        assert_eq!(lesser.cmp(&lesser), std::cmp::Ordering::Equal);
+
+        let mut boards = vec![greater, lesser, Quadboard::default()];
+        boards.sort();
+        assert_eq!(boards, vec![Quadboard::default(), lesser, greater]);
+    }
+
+    #[test]
+    fn new_zeroed_is_all_zero() {
+        #[derive(Debug, PartialEq)]
+        struct Square(u8);
+
+        impl EmptySquare for Square {}
+
+        impl From<Nibble> for Square {
+            fn from(value: Nibble) -> Self {
+                Square(value.get())
+            }
+        }
+
+        let qb = Quadboard::<Square>::new_zeroed();
+        assert_eq!(qb.get(Index::MAX), Square(0));
+        assert_eq!(qb.as_raw_quadboard(), &RawQuadboard::default());
+    }
+
+    #[test]
+    fn nibble_boards_can_be_zeroed() {
+        // Nibble has no Default impl, so this is the only way to get an empty board
+        let qb = Quadboard::<Nibble>::new_zeroed();
+        assert!(Index::all().all(|index| qb.get(index) == Nibble::ZERO));
+    }
+
+    #[test]
+    fn set_if_only_writes_on_match() {
+        let mut qb = Quadboard::<Cell>::default();
+        let index = 40.try_into().unwrap();
+
+        assert!(!qb.set_if(index, Cell(3), Cell(4)));
+        assert_eq!(qb.get(index), Cell(0));
+
+        assert!(qb.set_if(index, Cell(0), Cell(4)));
+        assert_eq!(qb.get(index), Cell(4));
+    }
+
+    #[test]
+    fn replace_where_and_replace_nibbles_agree() {
+        let mut qb = Quadboard::<Cell>::default();
+        for i in 0..64u8 {
+            qb.set(i.try_into().unwrap(), Cell(i % 16));
+        }
+
+        let mut by_predicate = qb;
+        let mut by_nibbles = qb;
+        let expected = 0x00F0_00F0_00F0_00F0;
+
+        let mask = by_predicate.replace_where(|_, cell| (4..8).contains(&cell.0), Cell(15));
+        assert_eq!(mask, expected);
+        assert_eq!(by_nibbles.replace_nibbles(0b1111_0000, Cell(15)), expected);
+        assert_eq!(by_predicate, by_nibbles);
+
+        let mask = by_predicate.replace_where(|index, _| index.get() == 0, Cell(9));
+        assert_eq!(mask, 1);
+        assert_eq!(by_predicate.get(Index::MIN), Cell(9));
+    }
+
+    #[test]
+    fn columns_fill_from_the_bottom() {
+        let mut qb = Quadboard::<Nibble>::new_zeroed();
+
+        for rank in 0..8u8 {
+            let index = qb.push_column(File::C, Nibble::ONE).unwrap();
+            assert_eq!(index.get(), 8 * rank + 2);
+        }
+
+        assert_eq!(
+            qb.push_column(File::C, Nibble::ONE),
+            Err(ColumnFullError(File::C))
+        );
+        assert_eq!(
+            qb.pop_column(File::C),
+            Some((58.try_into().unwrap(), Nibble::ONE))
+        );
+        assert_eq!(qb.pop_column(File::D), None);
+        assert_eq!(qb.push_column(File::C, Nibble::TWO).unwrap().get(), 58);
+    }
+
+    #[test]
+    fn array_conversions_round_trip() {
+        let cells: [Cell; 64] = std::array::from_fn(|i| Cell((i % 16) as u8));
+        let qb = Quadboard::from(cells);
+
+        assert_eq!(qb.get(17.try_into().unwrap()), Cell(1));
+        assert_eq!(qb.into_array(), cells);
+    }
+
+    #[test]
+    fn from_fn_and_collect_agree() {
+        let from_fn = Quadboard::from_fn(|index| Cell(index.get() / 4));
+        let collected: Quadboard<Cell> = (0..64).map(|i| Cell(i / 4)).collect();
+
+        assert_eq!(from_fn, collected);
+        assert_eq!(from_fn.get(Index::MAX), Cell(15));
+    }
+
+    #[test]
+    #[should_panic]
+    fn collect_panics_on_short_iterators() {
+        let _: Quadboard<Cell> = (0..63).map(|_| Cell(0)).collect();
+    }
+
+    #[test]
+    fn quadboard_is_hashable_without_hashable_elements() {
+        use std::collections::HashMap;
+
+        let mut repetitions = HashMap::new();
+        let qb = Quadboard::from_fn(|index| Cell(index.get() % 5));
+
+        *repetitions.entry(qb).or_insert(0) += 1;
+        *repetitions.entry(Quadboard::default()).or_insert(0) += 1;
+        *repetitions.entry(qb).or_insert(0) += 1;
+        assert_eq!(repetitions[&qb], 2);
+    }
+
+    #[test]
+    fn try_get_reports_invalid_nibbles() {
+        /// A value type which only accepts even nibbles.
+        #[derive(Debug, PartialEq, Eq)]
+        struct Even(u8);
+
+        impl TryFrom<Nibble> for Even {
+            type Error = u8;
+
+            fn try_from(value: Nibble) -> Result<Self, Self::Error> {
+                match value.get() % 2 {
+                    0 => Ok(Even(value.get())),
+                    _ => Err(value.get()),
+                }
+            }
+        }
+
+        impl EmptySquare for Even {}
+
+        let mut qb = Quadboard::<Even>::new_zeroed();
+        qb.inner.set(5.try_into().unwrap(), Nibble::THREE);
+        qb.inner.set(6.try_into().unwrap(), Nibble::TWELVE);
+
+        assert_eq!(qb.try_get(0.try_into().unwrap()), Ok(Even(0)));
+        assert_eq!(qb.try_get(5.try_into().unwrap()), Err(3));
+        assert_eq!(unsafe { qb.try_get_unchecked(6) }, Ok(Even(12)));
+    }
+
+    #[test]
+    fn try_from_raw_reports_every_invalid_index() {
+        /// A value type which only accepts nibbles below four.
+        #[derive(Debug, PartialEq, Eq)]
+        struct Small(u8);
+
+        impl TryFrom<Nibble> for Small {
+            type Error = ();
+
+            fn try_from(value: Nibble) -> Result<Self, Self::Error> {
+                match value.get() {
+                    0..4 => Ok(Small(value.get())),
+                    _ => Err(()),
+                }
+            }
+        }
+
+        let mut raw = RawQuadboard::splat(Nibble::THREE);
+        assert_eq!(raw.invalid_mask::<Small>(), 0);
+        assert!(Quadboard::<Small>::try_from_raw(raw).is_ok());
+
+        raw.set(9.try_into().unwrap(), Nibble::FOUR);
+        raw.set(40.try_into().unwrap(), Nibble::FIFTEEN);
+        let error = Quadboard::<Small>::try_from(raw).unwrap_err();
+        assert_eq!(error.mask(), (1 << 9) | (1 << 40));
+        assert_eq!(error.first().get(), 9);
+        assert_eq!(InvalidBoardError::new(0), None);
+        assert_eq!(raw.invalid_mask::<Nibble>(), 0);
+    }
+
+    #[test]
+    fn map_preserves_positions() {
+        let qb = Quadboard::from_fn(|index| Cell(index.get() % 7));
+        let mapped = qb.map(|Cell(value)| Cell(value * 2 + 1));
+
+        assert_eq!(
+            mapped,
+            Quadboard::from_fn(|index| Cell((index.get() % 7) * 2 + 1))
+        );
+    }
+
+    #[test]
+    fn try_map_reports_first_failing_index() {
+        let qb = Quadboard::from_fn(|index| Cell(index.get() % 7));
+        let halve = |Cell(value)| match value % 2 {
+            0 => Ok(Cell(value / 2)),
+            _ => Err(value),
+        };
+
+        let error = qb.try_map(halve).unwrap_err();
+        assert_eq!(error.index.get(), 1);
+        assert_eq!(error.error, 1);
+
+        let evens = Quadboard::from_fn(|index| Cell(index.get() % 4 * 2));
+        assert_eq!(
+            evens.try_map(halve),
+            Ok(Quadboard::from_fn(|index| Cell(index.get() % 4)))
+        );
+    }
+
+    #[test]
+    fn fill_and_clear_overwrite_every_element() {
+        let mut qb = Quadboard::from_fn(|index| Cell(index.get() % 16));
+
+        qb.fill(Cell(11));
+        assert!(qb.iter().all(|(_, value)| value == Cell(11)));
+
+        qb.clear();
+        assert_eq!(qb, Quadboard::default());
+    }
+
+    #[test]
+    fn random_with_threads_rng_through_every_index() {
+        let mut state = 0u8;
+        let qb = Quadboard::random_with(&mut state, |index, state| {
+            *state = state.wrapping_add(index.get());
+            Cell(*state % 16)
+        });
+
+        assert_eq!(state, (0..64u8).fold(0, u8::wrapping_add));
+        assert_eq!(qb.get(3.try_into().unwrap()), Cell(6));
+    }
+
+    #[test]
+    fn map_in_place_sees_every_index() {
+        let mut qb = Quadboard::from_fn(|index| Cell(index.get() % 4));
+        qb.map_in_place(|index, Cell(value)| match index.get() < 8 {
+            true => Cell(value + 10),
+            false => Cell(value),
+        });
+
+        assert_eq!(
+            qb,
+            Quadboard::from_fn(|index| match index.get() < 8 {
+                true => Cell(index.get() % 4 + 10),
+                false => Cell(index.get() % 4),
+            })
+        );
+    }
+
+    #[test]
+    fn predicate_queries_agree_with_iteration() {
+        let qb = Quadboard::from_fn(|index| Cell(index.get() % 3 * 4));
+
+        assert!(qb.contains(Cell(8)));
+        assert!(!qb.contains(Cell(1)));
+        assert!(qb.any(|Cell(value)| value > 4));
+        assert!(!qb.any(|Cell(value)| value > 8));
+        assert!(qb.all(|Cell(value)| value % 4 == 0));
+        assert!(!qb.all(|Cell(value)| value > 0));
+
+        let mask = qb.mask_where(|nibble| nibble.get() >= 4);
+        let expected = (0..64u8)
+            .filter(|i| i % 3 != 0)
+            .fold(0u64, |mask, i| mask | (1 << i));
+        assert_eq!(mask, expected);
+    }
+
+    #[test]
+    fn count_of_counts_matching_elements() {
+        let qb = Quadboard::from_fn(|index| Cell(index.get() % 5));
+
+        assert_eq!(qb.count_of(Cell(0)), 13);
+        assert_eq!(qb.count_of(Cell(4)), 12);
+        assert_eq!(qb.count_of(Cell(9)), 0);
+    }
+
+    #[test]
+    fn positions_of_finds_every_match() {
+        let qb = Quadboard::from_fn(|index| Cell(index.get() % 3));
+
+        assert_eq!(qb.first_index_of(Cell(2)).map(|index| index.get()), Some(2));
+        assert_eq!(qb.first_index_of(Cell(7)), None);
+        assert!(qb.positions_of(Cell(1)).all(|index| index.get() % 3 == 1));
+        assert_eq!(qb.positions_of(Cell(0)).len(), 22);
+    }
+
+    #[test]
+    fn runs_cover_the_whole_board() {
+        let mut qb = Quadboard::<Cell>::default();
+        qb.set(0.try_into().unwrap(), Cell(5));
+        qb.set(1.try_into().unwrap(), Cell(5));
+        qb.set(10.try_into().unwrap(), Cell(1));
+        qb.set(63.try_into().unwrap(), Cell(15));
+
+        let runs: Vec<_> = qb.runs().collect();
+        assert_eq!(
+            runs,
+            vec![
+                (Cell(5), 2),
+                (Cell(0), 8),
+                (Cell(1), 1),
+                (Cell(0), 52),
+                (Cell(15), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn runs_of_uniform_board_is_single_run() {
+        let qb = Quadboard::<Cell>::default();
+        assert_eq!(qb.runs().collect::<Vec<_>>(), vec![(Cell(0), 64)]);
+    }
+}
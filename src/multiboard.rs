@@ -0,0 +1,280 @@
+//! Untyped boards with an arbitrary number of channels.
+//!
+//! A [`MultiBoard<C>`] generalises the layout of a [`RawQuadboard`] to `C`
+//! channels, so that each element holds a `C`-bit value rather than a nibble.
+//! This is useful for values which don't fit in four bits, such as a piece
+//! together with some flags, or attack counts.
+
+use crate::index::Index;
+use crate::lanes::u64x4;
+use crate::raw_quadboard::RawQuadboard;
+
+/// A board with eight channels, storing one byte per element.
+pub type Octoboard = MultiBoard<8>;
+
+/// An untyped board of 64 `C`-bit values, held in `C` channels.
+///
+/// As with a [`RawQuadboard`], the `n`th channel holds the `n`th bit of
+/// every element, and bit `i` of each channel belongs to the element at
+/// index `i`. `C` must be between 1 and 64.
+///
+/// The channels are held in a plain array, but are manipulated four at a
+/// time with the same `u64x4` vectors as a [`RawQuadboard`] (a final group
+/// of fewer than four channels is padded with zeroes). A [`MultiBoard<4>`]
+/// converts losslessly to and from a [`RawQuadboard`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct MultiBoard<const C: usize> {
+    channels: [u64; C],
+}
+
+impl<const C: usize> Default for MultiBoard<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<RawQuadboard> for MultiBoard<4> {
+    fn from(value: RawQuadboard) -> Self {
+        Self::from_channels(value.into_channels())
+    }
+}
+
+impl From<MultiBoard<4>> for RawQuadboard {
+    fn from(value: MultiBoard<4>) -> Self {
+        RawQuadboard::from_channels(value.into_channels())
+    }
+}
+
+impl<const C: usize> MultiBoard<C> {
+    /// Checks that `C` is a supported number of channels.
+    const VALID_CHANNEL_COUNT: () =
+        assert!(C > 0 && C <= 64, "a MultiBoard must have 1 to 64 channels");
+
+    /// A mask of the element bits which are held by some channel.
+    const VALUE_MASK: u64 = match C {
+        64 => u64::MAX,
+        _ => (1 << C) - 1,
+    };
+
+    /// Constructs a new [`MultiBoard`] with every element set to zero.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self::from_channels([0; C])
+    }
+
+    /// Constructs a [`MultiBoard`] directly from its `u64` channels.
+    #[inline(always)]
+    pub const fn from_channels(channels: [u64; C]) -> Self {
+        let () = Self::VALID_CHANNEL_COUNT;
+        Self { channels }
+    }
+
+    /// Consumes `self` and returns an array of the underlying `u64` channels.
+    #[inline(always)]
+    pub const fn into_channels(self) -> [u64; C] {
+        self.channels
+    }
+
+    /// Returns a reference to the underlying `u64` channels.
+    #[inline(always)]
+    pub const fn as_channels(&self) -> &[u64; C] {
+        &self.channels
+    }
+
+    /// Returns a mutable reference to the underlying `u64` channels.
+    #[inline(always)]
+    pub fn as_channels_mut(&mut self) -> &mut [u64; C] {
+        &mut self.channels
+    }
+
+    /// Constructs a new [`MultiBoard`] with every element set to `value`.
+    ///
+    /// Bits of `value` at or above `C` are ignored.
+    #[inline(always)]
+    pub fn splat(value: u64) -> Self {
+        let mut board = Self::new();
+        board.update_groups(|_, numbers| Self::spread(value, numbers));
+        board
+    }
+
+    /// Returns the value of the element at `index`.
+    #[inline(always)]
+    pub fn get(&self, index: Index) -> u64 {
+        let i = index.get() as u64;
+
+        self.groups().fold(0, |value, (group, numbers)| {
+            let bits = ((group >> i) & u64x4::splat(1)) << numbers;
+            value | bits.to_array().iter().fold(0, |acc, bit| acc | bit)
+        })
+    }
+
+    /// Writes `value` to the element at `index`.
+    ///
+    /// Bits of `value` at or above `C` are ignored.
+    #[inline(always)]
+    pub fn set(&mut self, index: Index, value: u64) {
+        let i = index.get() as u64;
+
+        self.update_groups(|group, numbers| {
+            let bits = (u64x4::splat(value) >> numbers) & u64x4::splat(1);
+            (group & u64x4::splat(!(1 << i))) | (bits << i)
+        });
+    }
+
+    /// Returns a bitmask with a bit set at every index whose element is
+    /// equal to `value`.
+    ///
+    /// Bits of `value` at or above `C` are ignored.
+    #[inline(always)]
+    pub fn eq_mask(&self, value: u64) -> u64 {
+        let value = value & Self::VALUE_MASK;
+
+        self.groups().fold(u64::MAX, |mask, (group, numbers)| {
+            // each lane is all ones where its channel agrees with `value`;
+            // the padding lanes always agree, since both are zero
+            let spread = Self::spread(value, numbers);
+            let agree = !(group ^ spread);
+            mask & agree
+                .to_array()
+                .iter()
+                .fold(u64::MAX, |acc, lane| acc & lane)
+        })
+    }
+
+    /// Returns a bitmask with a bit set at every index whose element is
+    /// nonzero, i.e. the bitwise OR of the channels.
+    #[inline(always)]
+    pub fn occupancy(&self) -> u64 {
+        self.groups().fold(0, |mask, (group, _)| {
+            mask | group.to_array().iter().fold(0, |acc, lane| acc | lane)
+        })
+    }
+
+    /// Returns a new [`MultiBoard`] taking the elements of `other` at the
+    /// indices set in `mask`, and the elements of `self` everywhere else.
+    #[inline(always)]
+    pub fn blend(&self, mask: u64, other: &Self) -> Self {
+        let mask = u64x4::splat(mask);
+        let mut others = other.groups();
+        let mut board = *self;
+
+        board.update_groups(|group, _| {
+            let (other, _) = others.next().unwrap();
+            (group & !mask) | (other & mask)
+        });
+
+        board
+    }
+
+    /// Writes `value` to every index set in `mask`, leaving the other
+    /// elements unchanged.
+    #[inline(always)]
+    pub fn fill_masked(&mut self, mask: u64, value: u64) {
+        *self = self.blend(mask, &Self::splat(value));
+    }
+
+    /// Returns a vector whose lanes are all ones where the bit of `value`
+    /// numbered by the corresponding lane of `numbers` is set, and all zeroes
+    /// elsewhere.
+    #[inline(always)]
+    fn spread(value: u64, numbers: u64x4) -> u64x4 {
+        ((u64x4::splat(value) >> numbers) & u64x4::splat(1)) * u64x4::splat(u64::MAX)
+    }
+
+    /// Returns the number of each lane of the `k`th group of channels.
+    #[inline(always)]
+    fn lane_numbers(k: usize) -> u64x4 {
+        let first = 4 * k as u64;
+        u64x4::from_array([first, first + 1, first + 2, first + 3])
+    }
+
+    /// Returns an iterator over the channels in groups of four, along with
+    /// the number of each lane; the last group is padded with zeroes.
+    #[inline(always)]
+    fn groups(&self) -> impl Iterator<Item = (u64x4, u64x4)> + '_ {
+        self.channels.chunks(4).enumerate().map(|(k, chunk)| {
+            let mut lanes = [0; 4];
+            lanes[..chunk.len()].copy_from_slice(chunk);
+            (u64x4::from_array(lanes), Self::lane_numbers(k))
+        })
+    }
+
+    /// Replaces each group of four channels with the result of `f`, which is
+    /// given the group and the number of each of its lanes; the results for
+    /// padding lanes are discarded.
+    #[inline(always)]
+    fn update_groups(&mut self, mut f: impl FnMut(u64x4, u64x4) -> u64x4) {
+        for (k, chunk) in self.channels.chunks_mut(4).enumerate() {
+            let mut lanes = [0; 4];
+            lanes[..chunk.len()].copy_from_slice(chunk);
+
+            let updated = f(u64x4::from_array(lanes), Self::lane_numbers(k)).to_array();
+            chunk.copy_from_slice(&updated[..chunk.len()]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halfling::Nibble;
+
+    #[test]
+    fn octoboard_stores_bytes() {
+        let mut board = Octoboard::new();
+        let (a, b) = (Index::try_from(3).unwrap(), Index::try_from(62).unwrap());
+
+        board.set(a, 0xA5);
+        board.set(b, 0x1FF);
+        assert_eq!(board.get(a), 0xA5);
+        assert_eq!(board.get(b), 0xFF);
+        assert_eq!(board.eq_mask(0xA5), 1 << 3);
+        assert_eq!(board.eq_mask(0), !((1 << 3) | (1 << 62)));
+        assert_eq!(board.occupancy(), (1 << 3) | (1 << 62));
+
+        board.fill_masked(0xFF, 7);
+        assert_eq!(board.get(a), 7);
+        assert_eq!(board.eq_mask(7), 0xFF);
+    }
+
+    #[test]
+    fn four_channel_boards_agree_with_raw_quadboards() {
+        let mut rqb = RawQuadboard::default();
+        rqb.set(Index::try_from(9).unwrap(), Nibble::ELEVEN);
+        rqb.set(Index::try_from(40).unwrap(), Nibble::SIX);
+
+        let board = MultiBoard::<4>::from(rqb);
+        for index in Index::all() {
+            assert_eq!(board.get(index), rqb.get(index).get() as u64);
+        }
+
+        assert_eq!(RawQuadboard::from(board), rqb);
+        assert_eq!(
+            MultiBoard::<4>::splat(13),
+            RawQuadboard::splat(Nibble::THIRTEEN).into()
+        );
+    }
+
+    #[test]
+    fn five_channel_boards_pad_the_last_group() {
+        let mut board = MultiBoard::<5>::splat(0b10110);
+        let index = Index::try_from(20).unwrap();
+        assert_eq!(board.get(index), 0b10110);
+        assert_eq!(board.eq_mask(0b10110), u64::MAX);
+
+        board.set(index, 0b11111);
+        assert_eq!(board.get(index), 0b11111);
+        assert_eq!(board.eq_mask(0b11111), 1 << 20);
+        assert_eq!(board.as_channels()[4], u64::MAX);
+        assert_eq!(MultiBoard::<5>::new().occupancy(), 0);
+    }
+
+    #[test]
+    fn sixty_four_channel_boards_hold_whole_words() {
+        let mut board = MultiBoard::<64>::default();
+        board.set(Index::MAX, u64::MAX - 1);
+        assert_eq!(board.get(Index::MAX), u64::MAX - 1);
+        assert_eq!(board.eq_mask(u64::MAX - 1), 1 << 63);
+    }
+}
@@ -0,0 +1,116 @@
+//! Tentative writes against a base quadboard.
+
+use halfling::Nibble;
+
+use crate::index::Index;
+use crate::raw_quadboard::RawQuadboard;
+use crate::Quadboard;
+
+/// A layer of pending writes over a mutably borrowed [`Quadboard`].
+///
+/// Reads are served from the overlay wherever it has been written to, and
+/// from the base board otherwise. None of the writes are visible in the base
+/// board until [`OverlayQuadboard::commit`] is called, at which point they
+/// are all applied in a single masked update; dropping the overlay (or
+/// calling [`OverlayQuadboard::discard`]) leaves the base board untouched.
+#[derive(Debug)]
+pub struct OverlayQuadboard<'a, T> {
+    base: &'a mut Quadboard<T>,
+    pending: RawQuadboard,
+    written: u64,
+}
+
+impl<'a, T> OverlayQuadboard<'a, T> {
+    /// Creates an empty overlay over `base`.
+    pub fn new(base: &'a mut Quadboard<T>) -> Self {
+        Self {
+            base,
+            pending: RawQuadboard::default(),
+            written: 0,
+        }
+    }
+
+    /// Returns the value at `index` as seen through the overlay.
+    #[inline(always)]
+    pub fn get(&self, index: Index) -> T
+    where
+        Nibble: Into<T>,
+    {
+        match self.written & (1 << index.get()) {
+            0 => self.base.get(index),
+            _ => self.pending.get(index).into(),
+        }
+    }
+
+    /// Records a pending write of `value` to `index`.
+    #[inline(always)]
+    pub fn set(&mut self, index: Index, value: T)
+    where
+        T: Into<Nibble>,
+    {
+        self.pending.set(index, value.into());
+        self.written |= 1 << index.get();
+    }
+
+    /// Returns a reference to the base board, which does not reflect
+    /// any of the pending writes.
+    pub fn base(&self) -> &Quadboard<T> {
+        self.base
+    }
+
+    /// Returns a bitmask of the indices with pending writes.
+    pub const fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Returns `true` if there are no pending writes.
+    pub const fn is_clean(&self) -> bool {
+        self.written == 0
+    }
+
+    /// Returns a copy of the board as seen through the overlay.
+    pub fn to_quadboard(&self) -> Quadboard<T> {
+        Quadboard {
+            inner: self.base.inner.blend(self.written, &self.pending),
+            _data: Default::default(),
+        }
+    }
+
+    /// Applies all the pending writes to the base board.
+    pub fn commit(self) {
+        self.base.inner = self.base.inner.blend(self.written, &self.pending);
+    }
+
+    /// Drops all the pending writes, leaving the base board unchanged.
+    pub fn discard(self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlay_writes_are_only_visible_after_commit() {
+        let mut board = Quadboard::<Nibble> {
+            inner: RawQuadboard::splat(Nibble::ONE),
+            _data: Default::default(),
+        };
+
+        let index: Index = 20.try_into().unwrap();
+        let mut overlay = OverlayQuadboard::new(&mut board);
+        overlay.set(index, Nibble::NINE);
+
+        assert_eq!(overlay.get(index), Nibble::NINE);
+        assert_eq!(overlay.base().get(index), Nibble::ONE);
+        assert_eq!(overlay.written(), 1 << 20);
+
+        overlay.discard();
+        assert_eq!(board.get(index), Nibble::ONE);
+
+        let mut overlay = OverlayQuadboard::new(&mut board);
+        overlay.set(index, Nibble::NINE);
+        overlay.commit();
+        assert_eq!(board.get(index), Nibble::NINE);
+        assert_eq!(board.get(Index::MIN), Nibble::ONE);
+    }
+}
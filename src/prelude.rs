@@ -0,0 +1,20 @@
+//! Re-exports of the core types of this crate.
+//!
+//! This module is intended to be glob-imported, as in the following.
+//!
+//! ```
+//! use quadboard::prelude::*;
+//!
+//! let mut board = RawQuadboard::default();
+//! board.set(Index::MAX, Nibble::SEVEN);
+//! ```
+
+// with the `derive` feature, this also re-exports the NibbleEncoding derive macro
+pub use crate::codec::{EmptySquare, NibbleEncoding};
+pub use crate::counter::CounterBoard;
+pub use crate::direction::Direction;
+pub use crate::index::{File, Index, Rank};
+pub use crate::quadboard81::{Index81, Quadboard81, RawQuadboard81};
+pub use crate::raw_quadboard::{Channel, RawQuadboard};
+pub use crate::Nibble;
+pub use crate::Quadboard;
@@ -0,0 +1,556 @@
+//! Quadboards with 81 squares, for 9×9 games such as shogi.
+//!
+//! A [`RawQuadboard81`] is a [`RawBlockQuadboard`] made up of two
+//! [`RawQuadboard`] blocks: the first holds squares 0 through 63, and the
+//! first 17 elements of the second hold squares 64 through 80. The remaining
+//! elements of the second block are always zero, so that equality of boards
+//! coincides with equality of their squares.
+//!
+//! Squares are numbered rank by rank, so that the square at file `f` and rank
+//! `r` (both counted from 0) has index `9 * r + f`.
+
+use core::marker::PhantomData;
+
+use halfling::Nibble;
+
+use crate::blocks::RawBlockQuadboard;
+use crate::codec::EmptySquare;
+use crate::raw_quadboard::RawQuadboard;
+
+/// The set of possible values that an [`Index81`] may take, ranging from 0
+/// (inclusive) to 81 (exclusive).
+///
+/// As with the values of an [`Index`](crate::index::Index), this allows the
+/// compiler to apply the niche value optimisation to an [`Index81`].
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[repr(u8)]
+enum AllowedIndex81Value {
+    _00,
+    _01,
+    _02,
+    _03,
+    _04,
+    _05,
+    _06,
+    _07,
+    _08,
+    _09,
+    _0A,
+    _0B,
+    _0C,
+    _0D,
+    _0E,
+    _0F,
+    _10,
+    _11,
+    _12,
+    _13,
+    _14,
+    _15,
+    _16,
+    _17,
+    _18,
+    _19,
+    _1A,
+    _1B,
+    _1C,
+    _1D,
+    _1E,
+    _1F,
+    _20,
+    _21,
+    _22,
+    _23,
+    _24,
+    _25,
+    _26,
+    _27,
+    _28,
+    _29,
+    _2A,
+    _2B,
+    _2C,
+    _2D,
+    _2E,
+    _2F,
+    _30,
+    _31,
+    _32,
+    _33,
+    _34,
+    _35,
+    _36,
+    _37,
+    _38,
+    _39,
+    _3A,
+    _3B,
+    _3C,
+    _3D,
+    _3E,
+    _3F,
+    _40,
+    _41,
+    _42,
+    _43,
+    _44,
+    _45,
+    _46,
+    _47,
+    _48,
+    _49,
+    _4A,
+    _4B,
+    _4C,
+    _4D,
+    _4E,
+    _4F,
+    _50,
+}
+
+/// A valid index into a [`Quadboard81`] or [`RawQuadboard81`], ranging from
+/// 0 (inclusive) to 81 (exclusive).
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[repr(transparent)]
+pub struct Index81(AllowedIndex81Value);
+
+/// The unit error produced when a numeric conversion into an [`Index81`] fails.
+#[derive(Debug)]
+pub struct InvalidIndex81Error;
+
+impl core::fmt::Display for InvalidIndex81Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Attempted to construct an Index81 with a value greater than 80."
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidIndex81Error {}
+
+impl TryFrom<u8> for Index81 {
+    type Error = InvalidIndex81Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value < 81 {
+            true => Ok(unsafe { Self::new_unchecked(value) }),
+            false => Err(InvalidIndex81Error),
+        }
+    }
+}
+
+impl From<Index81> for u8 {
+    fn from(value: Index81) -> Self {
+        value.get()
+    }
+}
+
+impl Index81 {
+    /// The minimum value representable by an [`Index81`], i.e. 0.
+    pub const MIN: Self = Index81(AllowedIndex81Value::_00);
+
+    /// The maximum value representable by an [`Index81`], i.e. 80.
+    pub const MAX: Self = Index81(AllowedIndex81Value::_50);
+
+    /// Returns the value of `self` as a `u8`.
+    pub const fn get(&self) -> u8 {
+        self.0 as u8
+    }
+
+    /// Constructs an [`Index81`] without checking the validity of `value`.
+    ///
+    /// # Safety
+    /// `value` must be less than 81.
+    pub const unsafe fn new_unchecked(value: u8) -> Self {
+        debug_assert!(value < 81);
+        unsafe { core::mem::transmute(value) }
+    }
+
+    /// Returns the file (column) of `self`, from 0 to 8.
+    pub const fn file(&self) -> u8 {
+        self.get() % 9
+    }
+
+    /// Returns the rank (row) of `self`, from 0 to 8.
+    pub const fn rank(&self) -> u8 {
+        self.get() / 9
+    }
+}
+
+/// An untyped quadboard of 81 [`Nibble`] values.
+///
+/// This is a [`RawBlockQuadboard<81, 2>`] indexed by [`Index81`], with the
+/// addition of the transforms of a 9×9 board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[repr(transparent)]
+pub struct RawQuadboard81(RawBlockQuadboard<81, 2>);
+
+impl RawQuadboard81 {
+    /// Creates a new [`RawQuadboard81`] with every element set to the zero
+    /// nibble.
+    pub const fn new() -> Self {
+        Self(RawBlockQuadboard::new())
+    }
+
+    /// Creates a new [`RawQuadboard81`] with each element set to `value`.
+    pub fn splat(value: Nibble) -> Self {
+        Self(RawBlockQuadboard::splat(value))
+    }
+
+    /// Returns the two [`RawQuadboard`] blocks making up `self`.
+    pub const fn as_blocks(&self) -> [&RawQuadboard; 2] {
+        let [low, high] = self.0.as_blocks();
+        [low, high]
+    }
+
+    /// Returns the underlying [`RawBlockQuadboard`].
+    pub const fn as_block_quadboard(&self) -> &RawBlockQuadboard<81, 2> {
+        &self.0
+    }
+
+    /// Returns the [`Nibble`] at `index`.
+    #[inline(always)]
+    pub fn get(&self, index: Index81) -> Nibble {
+        unsafe { self.get_unchecked(index.get()) }
+    }
+
+    /// Sets the value of `self` at `index` to `value`.
+    #[inline(always)]
+    pub fn set(&mut self, index: Index81, value: Nibble) {
+        unsafe { self.set_unchecked(index.get(), value) }
+    }
+
+    /// Returns the [`Nibble`] at `index` without bounds checking.
+    ///
+    /// # Safety
+    /// `index` must be strictly less than 81.
+    #[inline(always)]
+    pub unsafe fn get_unchecked(&self, index: u8) -> Nibble {
+        unsafe { self.0.get_unchecked(index as usize) }
+    }
+
+    /// Writes `value` to `index` without bounds checking.
+    ///
+    /// # Safety
+    /// `index` must be strictly less than 81.
+    #[inline(always)]
+    pub unsafe fn set_unchecked(&mut self, index: u8, value: Nibble) {
+        unsafe { self.0.set_unchecked(index as usize, value) }
+    }
+
+    /// Returns a bitmask with a bit set at every index whose element is
+    /// equal to `value`.
+    ///
+    /// Bit `i` of the mask corresponds to the square with index `i`, so only
+    /// the lowest 81 bits may be set.
+    #[inline(always)]
+    pub fn eq_mask(&self, value: Nibble) -> u128 {
+        let [low, high] = self.0.as_blocks();
+        let padding = (1 << 17) - 1;
+
+        low.eq_mask(value) as u128 | (((high.eq_mask(value) & padding) as u128) << 64)
+    }
+
+    /// Returns a new board with each square moved to `f(index)`.
+    fn permute(&self, f: impl Fn(u8, u8) -> (u8, u8)) -> Self {
+        let mut board = Self::new();
+
+        for index in 0..81u8 {
+            let (file, rank) = f(index % 9, index / 9);
+            unsafe { board.set_unchecked(9 * rank + file, self.get_unchecked(index)) };
+        }
+
+        board
+    }
+
+    /// Mirrors `self` vertically, exchanging the first and last ranks.
+    pub fn flip_vertical(&self) -> Self {
+        self.permute(|file, rank| (file, 8 - rank))
+    }
+
+    /// Mirrors `self` horizontally, exchanging the first and last files.
+    pub fn flip_horizontal(&self) -> Self {
+        self.permute(|file, rank| (8 - file, rank))
+    }
+
+    /// Rotates `self` by 180 degrees.
+    pub fn rotate_180(&self) -> Self {
+        self.permute(|file, rank| (8 - file, 8 - rank))
+    }
+}
+
+/// A fixed-length buffer of 81 `T` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quadboard81<T> {
+    inner: RawQuadboard81,
+    _data: PhantomData<T>,
+}
+
+impl<T> Default for Quadboard81<T>
+where
+    T: Default + Into<Nibble>,
+{
+    fn default() -> Self {
+        Self {
+            inner: RawQuadboard81::splat(T::default().into()),
+            _data: PhantomData,
+        }
+    }
+}
+
+impl<T> From<[T; 81]> for Quadboard81<T>
+where
+    T: Into<Nibble>,
+{
+    fn from(value: [T; 81]) -> Self {
+        let mut inner = RawQuadboard81::new();
+
+        for (index, element) in (0..81u8).zip(value) {
+            unsafe { inner.set_unchecked(index, element.into()) };
+        }
+
+        Self::from_raw(inner)
+    }
+}
+
+impl<T> Quadboard81<T> {
+    /// Creates a new [`Quadboard81`] with every element set to the zero nibble.
+    pub const fn new_zeroed() -> Self
+    where
+        T: EmptySquare,
+    {
+        Self::from_raw(RawQuadboard81::new())
+    }
+
+    /// Returns the value at the given [`Index81`].
+    #[inline(always)]
+    pub fn get(&self, index: Index81) -> T
+    where
+        Nibble: Into<T>,
+    {
+        unsafe { self.get_unchecked(index.get()) }
+    }
+
+    /// Reads the [`Nibble`] at the given index and
+    /// passes it to `T::from`.
+    ///
+    /// # Safety
+    /// `index` must be less than 81.
+    #[inline(always)]
+    pub unsafe fn get_unchecked(&self, index: u8) -> T
+    where
+        Nibble: Into<T>,
+    {
+        unsafe { self.inner.get_unchecked(index) }.into()
+    }
+
+    /// Reads the [`Nibble`] at the given [`Index81`] and passes it to
+    /// `T::try_from`.
+    ///
+    /// This is the fallible equivalent of [`Quadboard81::get`], for encodings
+    /// in which some nibbles do not correspond to a `T` value.
+    #[inline(always)]
+    pub fn try_get(&self, index: Index81) -> Result<T, T::Error>
+    where
+        T: TryFrom<Nibble>,
+    {
+        unsafe { self.try_get_unchecked(index.get()) }
+    }
+
+    /// Reads the [`Nibble`] at the given index and
+    /// passes it to `T::try_from`.
+    ///
+    /// # Safety
+    /// `index` must be less than 81.
+    #[inline(always)]
+    pub unsafe fn try_get_unchecked(&self, index: u8) -> Result<T, T::Error>
+    where
+        T: TryFrom<Nibble>,
+    {
+        T::try_from(unsafe { self.inner.get_unchecked(index) })
+    }
+
+    /// Converts `value` into a [`Nibble`] and writes the
+    /// resulting `T` value to the element at `index`.
+    #[inline(always)]
+    pub fn set(&mut self, index: Index81, value: T)
+    where
+        T: Into<Nibble>,
+    {
+        unsafe { self.set_unchecked(index.get(), value) };
+    }
+
+    /// Overwrites every element of `self` with `value`.
+    #[inline(always)]
+    pub fn fill(&mut self, value: T)
+    where
+        T: Into<Nibble>,
+    {
+        self.inner = RawQuadboard81::splat(value.into());
+    }
+
+    /// Converts `value` into a [`Nibble`] and writes the
+    /// resulting `T` value to `index` without bounds checking.
+    ///
+    /// # Safety
+    /// `index` must be strictly less than 81.
+    #[inline(always)]
+    pub unsafe fn set_unchecked(&mut self, index: u8, value: T)
+    where
+        T: Into<Nibble>,
+    {
+        unsafe { self.inner.set_unchecked(index, value.into()) };
+    }
+
+    /// Returns a bitmask with a bit set at every index whose element is
+    /// encoded by the same [`Nibble`] as `value`.
+    ///
+    /// See [`RawQuadboard81::eq_mask`] for details.
+    #[inline(always)]
+    pub fn mask_of(&self, value: T) -> u128
+    where
+        T: Into<Nibble>,
+    {
+        self.inner.eq_mask(value.into())
+    }
+
+    /// Returns the number of elements of `self` encoded by the same
+    /// [`Nibble`] as `value`.
+    #[inline(always)]
+    pub fn count_of(&self, value: T) -> u32
+    where
+        T: Into<Nibble>,
+    {
+        self.mask_of(value).count_ones()
+    }
+
+    /// Returns an iterator over the `(Index81, T)` pairs of `self`, in order
+    /// of increasing [`Index81`].
+    pub fn iter(&self) -> impl Iterator<Item = (Index81, T)> + '_
+    where
+        Nibble: Into<T>,
+    {
+        (0..81u8).map(|index| {
+            let index = unsafe { Index81::new_unchecked(index) };
+            (index, self.get(index))
+        })
+    }
+
+    /// Mirrors `self` vertically, exchanging the first and last ranks.
+    pub fn flip_vertical(&self) -> Self {
+        self.with_inner(self.inner.flip_vertical())
+    }
+
+    /// Mirrors `self` horizontally, exchanging the first and last files.
+    pub fn flip_horizontal(&self) -> Self {
+        self.with_inner(self.inner.flip_horizontal())
+    }
+
+    /// Rotates `self` by 180 degrees.
+    pub fn rotate_180(&self) -> Self {
+        self.with_inner(self.inner.rotate_180())
+    }
+
+    /// Wraps `inner` in a typed board, without checking that its elements
+    /// encode `T` values.
+    #[inline(always)]
+    pub const fn from_raw(inner: RawQuadboard81) -> Self {
+        Self {
+            inner,
+            _data: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the underlying [`RawQuadboard81`].
+    #[inline(always)]
+    pub const fn as_raw_quadboard(&self) -> &RawQuadboard81 {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying [`RawQuadboard81`].
+    #[inline(always)]
+    pub fn as_raw_quadboard_mut(&mut self) -> &mut RawQuadboard81 {
+        &mut self.inner
+    }
+
+    /// Wraps `inner` in a typed board.
+    const fn with_inner(&self, inner: RawQuadboard81) -> Self {
+        Self::from_raw(inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index81_rejects_out_of_range_values() {
+        assert!(Index81::try_from(80).is_ok());
+        assert!(Index81::try_from(81).is_err());
+    }
+
+    #[test]
+    fn index81_has_a_niche() {
+        assert_eq!(std::mem::size_of::<Option<Index81>>(), 1);
+        assert!((0..81).all(|value| Index81::try_from(value).unwrap().get() == value));
+    }
+
+    #[test]
+    fn quadboard81_get_and_set_span_both_blocks() {
+        let mut board = Quadboard81::<Nibble>::new_zeroed();
+        board.set(Index81::MIN, Nibble::ONE);
+        board.set(63.try_into().unwrap(), Nibble::TWO);
+        board.set(Index81::MAX, Nibble::THREE);
+
+        assert_eq!(board.get(Index81::MIN), Nibble::ONE);
+        assert_eq!(board.get(63.try_into().unwrap()), Nibble::TWO);
+        assert_eq!(board.get(Index81::MAX), Nibble::THREE);
+        assert_eq!(board.get(64.try_into().unwrap()), Nibble::ZERO);
+    }
+
+    #[test]
+    fn quadboard81_typed_queries_cover_every_square() {
+        let mut board = Quadboard81::from(core::array::from_fn::<_, 81, _>(|index| {
+            Nibble::try_from((index % 3) as u8).unwrap()
+        }));
+
+        assert_eq!(board.count_of(Nibble::TWO), 27);
+        assert_eq!(board.mask_of(Nibble::ZERO) & 0b1001, 0b1001);
+        assert_eq!(board.mask_of(Nibble::ZERO) >> 81, 0);
+        assert_eq!(board.try_get(Index81::MAX), Ok(Nibble::TWO));
+        assert!(board
+            .iter()
+            .all(|(index, value)| value.get() == index.get() % 3));
+
+        board.fill(Nibble::SEVEN);
+        assert_eq!(board.count_of(Nibble::SEVEN), 81);
+        board.as_raw_quadboard_mut().set(Index81::MIN, Nibble::ONE);
+        assert_eq!(board.get(Index81::MIN), Nibble::ONE);
+    }
+
+    #[test]
+    fn quadboard81_splat_leaves_padding_zero() {
+        let board = RawQuadboard81::splat(Nibble::MAX);
+        let high = board.as_blocks()[1].into_channels();
+        assert!(high.iter().all(|&channel| channel == (1 << 17) - 1));
+    }
+
+    #[test]
+    fn quadboard81_transforms_move_corners() {
+        let mut board = Quadboard81::<Nibble>::new_zeroed();
+        board.set(Index81::MIN, Nibble::NINE);
+
+        assert_eq!(
+            board.flip_vertical().get(72.try_into().unwrap()),
+            Nibble::NINE
+        );
+        assert_eq!(
+            board.flip_horizontal().get(8.try_into().unwrap()),
+            Nibble::NINE
+        );
+        assert_eq!(board.rotate_180().get(Index81::MAX), Nibble::NINE);
+        assert_eq!(board.rotate_180().rotate_180(), board);
+    }
+}
@@ -1,12 +1,95 @@
 //! Untyped quadboards storing [`Nibble`] values.
 
-use crate::index::Index;
+use crate::direction::Direction;
+use crate::index::{File, Index, Rank};
+use crate::lanes::u64x4;
 use halfling::Nibble;
-use std::simd::u64x4;
+
+/// One of the four channels of a [`RawQuadboard`], named by the weight of
+/// the nibble bit it holds.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+#[repr(u8)]
+pub enum Channel {
+    /// The channel holding the least significant bit of each nibble.
+    Ones = 0,
+    /// The channel holding the second bit of each nibble.
+    Twos = 1,
+    /// The channel holding the third bit of each nibble.
+    Fours = 2,
+    /// The channel holding the most significant bit of each nibble.
+    Eights = 3,
+}
+
+impl Channel {
+    /// All four channels, in order of increasing weight.
+    pub const ALL: [Channel; 4] = [
+        Channel::Ones,
+        Channel::Twos,
+        Channel::Fours,
+        Channel::Eights,
+    ];
+
+    /// Returns the position of this channel in [`RawQuadboard::as_channels`].
+    #[inline(always)]
+    pub const fn get(self) -> usize {
+        self as usize
+    }
+
+    /// Returns the weight of the nibble bit held by this channel.
+    #[inline(always)]
+    pub const fn weight(self) -> u8 {
+        1 << (self as u8)
+    }
+}
+
+/// The error produced when decoding a [`RawQuadboard`] from the compact
+/// encoding fails.
+#[derive(Debug, PartialEq, Eq)]
+pub enum InvalidCompactEncodingError {
+    /// The input ended before the end of the encoding.
+    Truncated,
+    /// The encoding stored a zero element at an occupied index, or nonzero
+    /// padding after its last element, and so is not canonical.
+    NonCanonical,
+}
+
+impl core::fmt::Display for InvalidCompactEncodingError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "The compact board encoding is truncated."),
+            Self::NonCanonical => write!(f, "The compact board encoding is not canonical."),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidCompactEncodingError {}
 
 /// An untyped quadboard, effectively storing 64
-/// [`Nibble`] values in a [std::simd::u64x4].
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+/// [`Nibble`] values in a `u64x4`.
+///
+/// With the `simd` feature enabled (as it is by default), the channels are
+/// held in a `core::simd::u64x4`; otherwise they are held in a plain array
+/// and manipulated with scalar instructions.
+///
+/// The `n`th channel holds the `n`th bit (counting from the least
+/// significant) of every nibble, and bit `i` of each channel belongs to
+/// the nibble at index `i`. The in-memory byte order of the channels is
+/// platform-dependent; use [`RawQuadboard::to_le_bytes`] and friends for
+/// a portable representation.
+///
+/// # Layout
+/// A [`RawQuadboard`] is guaranteed to be exactly 32 bytes in size with an
+/// alignment of 32 bytes, to contain no padding, and to have every bit pattern
+/// valid; its memory is precisely the four channels, laid out in order as
+/// native-endian `u64`s. It may therefore be soundly reinterpreted to and from
+/// `[u64; 4]` (subject to alignment), or from a suitably aligned `[u8; 32]`.
+///
+/// The alignment is enforced by the representation of [`RawQuadboard`]
+/// itself, so it holds whether or not the `simd` feature is enabled, and does
+/// not depend on the alignment chosen for `core::simd::u64x4`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+#[repr(C, align(32))]
 pub struct RawQuadboard {
     channels: u64x4,
 }
@@ -24,9 +107,263 @@ impl RawQuadboard {
         self.channels.as_array()
     }
 
+    /// Constructs a [`RawQuadboard`] with uniformly distributed elements,
+    /// drawing one `u64` from `rng` for each channel.
+    ///
+    /// The random number generator is passed as a closure, so that with
+    /// `rand` (for example) it can be given as `&mut || rng.next_u64()`.
+    #[inline(always)]
+    pub fn random(rng: &mut impl FnMut() -> u64) -> Self {
+        Self::from_channels([rng(), rng(), rng(), rng()])
+    }
+
+    /// Returns a mutable reference to the underlying `u64` channels.
+    #[inline(always)]
+    pub fn as_channels_mut(&mut self) -> &mut [u64; 4] {
+        self.channels.as_mut_array()
+    }
+
+    /// Constructs a [`RawQuadboard`] directly from its `u64` channels.
+    ///
+    /// This is the inverse of [`RawQuadboard::into_channels`].
+    #[inline(always)]
+    pub const fn from_channels(channels: [u64; 4]) -> Self {
+        Self {
+            channels: u64x4::from_array(channels),
+        }
+    }
+
+    /// Constructs a [`RawQuadboard`] directly from a vector of its channels.
+    ///
+    /// # Channel Ordering
+    /// Lane `n` of the vector is the `n`th channel, holding the bit of
+    /// weight `2^n` of every nibble (see [`Channel`]), and bit `i` of each
+    /// lane belongs to the nibble at index `i`. This ordering is part of the
+    /// stable API, and is the same as that of [`RawQuadboard::from_channels`].
+    #[cfg(feature = "simd")]
+    #[inline(always)]
+    pub const fn from_simd(channels: core::simd::u64x4) -> Self {
+        Self { channels }
+    }
+
+    /// Consumes `self` and returns the vector of its channels, in the order
+    /// described by [`RawQuadboard::from_simd`].
+    #[cfg(feature = "simd")]
+    #[inline(always)]
+    pub const fn into_simd(self) -> core::simd::u64x4 {
+        self.channels
+    }
+
+    /// Returns a reference to the vector of channels underlying `self`, in
+    /// the order described by [`RawQuadboard::from_simd`].
+    #[cfg(feature = "simd")]
+    #[inline(always)]
+    pub const fn as_simd(&self) -> &core::simd::u64x4 {
+        &self.channels
+    }
+
+    /// Returns a mutable reference to the vector of channels underlying
+    /// `self`, in the order described by [`RawQuadboard::from_simd`].
+    ///
+    /// Since every bit pattern is a valid [`RawQuadboard`], any value may be
+    /// written through this reference.
+    #[cfg(feature = "simd")]
+    #[inline(always)]
+    pub fn as_simd_mut(&mut self) -> &mut core::simd::u64x4 {
+        &mut self.channels
+    }
+
+    /// Returns the given [`Channel`] of `self`.
+    #[inline(always)]
+    pub const fn channel(&self, channel: Channel) -> u64 {
+        self.as_channels()[channel.get()]
+    }
+
+    /// Returns a copy of `self` with the given [`Channel`] replaced by `value`.
+    #[inline(always)]
+    pub const fn with_channel(&self, channel: Channel, value: u64) -> Self {
+        let mut channels = self.into_channels();
+        channels[channel.get()] = value;
+        Self::from_channels(channels)
+    }
+
+    /// Overwrites the channel at `index` with `value`.
+    ///
+    /// # Panics
+    /// Panics if `index` is not less than 4.
+    #[inline(always)]
+    pub fn set_channel(&mut self, index: usize, value: u64) {
+        self.as_channels_mut()[index] = value;
+    }
+
+    /// Returns the memory representation of `self` as a byte array, with
+    /// the channels in order and each channel in little-endian byte order.
+    ///
+    /// This is the canonical encoding used for persistence by this crate,
+    /// and is independent of the native endianness of the target platform.
+    ///
+    /// # Encoding
+    /// Bit `n` of the element at index `i` is stored in bit `i % 8` of byte
+    /// `8 * n + i / 8`, so the first eight bytes hold the least significant
+    /// channel with the first rank in the first byte. This layout is stable
+    /// and will not change between versions of this crate.
+    pub const fn to_le_bytes(&self) -> [u8; 32] {
+        let channels = self.as_channels();
+        let mut bytes = [0u8; 32];
+        let mut i = 0;
+
+        while i < 4 {
+            let channel = channels[i].to_le_bytes();
+            let mut j = 0;
+
+            while j < 8 {
+                bytes[8 * i + j] = channel[j];
+                j += 1;
+            }
+
+            i += 1;
+        }
+
+        bytes
+    }
+
+    /// Returns the memory representation of `self` as a byte array, with
+    /// the channels in order and each channel in big-endian byte order.
+    pub const fn to_be_bytes(&self) -> [u8; 32] {
+        self.swap_channel_bytes().to_le_bytes()
+    }
+
+    /// Constructs a [`RawQuadboard`] from the little-endian representation
+    /// produced by [`RawQuadboard::to_le_bytes`].
+    pub const fn from_le_bytes(bytes: [u8; 32]) -> Self {
+        let mut channels = [0u64; 4];
+        let mut i = 0;
+
+        while i < 4 {
+            let mut channel = [0u8; 8];
+            let mut j = 0;
+
+            while j < 8 {
+                channel[j] = bytes[8 * i + j];
+                j += 1;
+            }
+
+            channels[i] = u64::from_le_bytes(channel);
+            i += 1;
+        }
+
+        Self::from_channels(channels)
+    }
+
+    /// Constructs a [`RawQuadboard`] from the big-endian representation
+    /// produced by [`RawQuadboard::to_be_bytes`].
+    pub const fn from_be_bytes(bytes: [u8; 32]) -> Self {
+        Self::from_le_bytes(bytes).swap_channel_bytes()
+    }
+
+    /// Reverses the byte order of each channel, converting between the
+    /// little- and big-endian byte representations.
+    const fn swap_channel_bytes(&self) -> Self {
+        let mut channels = *self.as_channels();
+        let mut i = 0;
+
+        while i < 4 {
+            channels[i] = channels[i].swap_bytes();
+            i += 1;
+        }
+
+        Self::from_channels(channels)
+    }
+
+    /// Returns a mask of the indices whose elements do not encode a `T`.
+    ///
+    /// Each of the sixteen nibbles is decoded once to determine whether it
+    /// is valid for `T`, and the equality masks of the invalid nibbles are
+    /// then combined, so the elements themselves are never decoded.
+    pub fn invalid_mask<T: TryFrom<Nibble>>(&self) -> u64 {
+        let invalid = (0..16u8)
+            .filter(|&value| T::try_from(unsafe { Nibble::new_unchecked(value) }).is_err())
+            .fold(0u16, |acc, value| acc | (1 << value));
+
+        self.values_mask(invalid)
+    }
+
+    /// Returns the length in bytes of the compact encoding of `self`.
+    #[inline(always)]
+    pub const fn compact_len(&self) -> usize {
+        8 + (self.occupancy().count_ones() as usize).div_ceil(2)
+    }
+
+    /// Appends the compact encoding of `self` to `out`.
+    ///
+    /// # Encoding
+    /// The compact encoding is the [occupancy](RawQuadboard::occupancy)
+    /// bitmask as a little-endian `u64`, followed by the nonzero elements in
+    /// order of increasing [`Index`], packed two to a byte with the first in
+    /// the lower four bits. If there are an odd number of nonzero elements,
+    /// the last byte is padded with zero. This takes between 8 and 40 bytes,
+    /// and is shorter than [`RawQuadboard::to_le_bytes`] whenever fewer than
+    /// 47 elements are nonzero.
+    #[cfg(feature = "std")]
+    pub fn encode_compact(&self, out: &mut Vec<u8>) {
+        let occupancy = self.occupancy();
+        let nibbles = self.to_nibble_bytes();
+        out.reserve(self.compact_len());
+        out.extend_from_slice(&occupancy.to_le_bytes());
+
+        let mut occupied = crate::iter::Indices::new(occupancy);
+        while let Some(low) = occupied.next() {
+            let high = occupied
+                .next()
+                .map_or(0, |index| nibbles[index.get() as usize]);
+            out.push(nibbles[low.get() as usize] | (high << 4));
+        }
+    }
+
+    /// Decodes a [`RawQuadboard`] from the start of `bytes`, as encoded by
+    /// [`RawQuadboard::encode_compact`], returning it with the remaining bytes.
+    pub fn decode_compact(bytes: &[u8]) -> Result<(Self, &[u8]), InvalidCompactEncodingError> {
+        let (occupancy, rest) = match bytes.split_first_chunk::<8>() {
+            Some((occupancy, rest)) => (u64::from_le_bytes(*occupancy), rest),
+            None => return Err(InvalidCompactEncodingError::Truncated),
+        };
+
+        let count = occupancy.count_ones() as usize;
+        let packed_len = count.div_ceil(2);
+        if rest.len() < packed_len {
+            return Err(InvalidCompactEncodingError::Truncated);
+        }
+
+        let (packed, rest) = rest.split_at(packed_len);
+        let mut nibbles = [0u8; 64];
+
+        for (k, index) in crate::iter::Indices::new(occupancy).enumerate() {
+            let value = (packed[k / 2] >> (4 * (k % 2))) & 0xF;
+
+            match value {
+                0 => return Err(InvalidCompactEncodingError::NonCanonical),
+                _ => nibbles[index.get() as usize] = value,
+            }
+        }
+
+        if count % 2 == 1 && packed[packed_len - 1] >> 4 != 0 {
+            return Err(InvalidCompactEncodingError::NonCanonical);
+        }
+
+        Ok((unsafe { Self::from_nibble_bytes(nibbles) }, rest))
+    }
+
+    /// Computes the CRC-32 checksum of `self`.
+    ///
+    /// The checksum is computed over the canonical encoding of `self`, as
+    /// given by [`RawQuadboard::to_le_bytes`].
+    pub fn crc32(&self) -> u32 {
+        crate::checksum::crc32(&self.to_le_bytes())
+    }
+
     /// Creates a new [`RawQuadboard`] with each element set to `value`.
     #[inline(always)]
-    pub fn splat(value: Nibble) -> Self {
+    pub const fn splat(value: Nibble) -> Self {
         let value: u8 = value.get();
 
         // extract the individual bits from the given value
@@ -41,7 +378,6 @@ impl RawQuadboard {
         // 000000000000000000000000000000000000000000000000000000000000000w
         // ^              ^               ^               ^               ^
         // └ bit 64       └ bit 48        └ bit 32        └ bit 16        └ bit 1
-        let bit_channels = u64x4::from_array([bit1, bit2, bit3, bit4]);
 
         // choose either u64::MAX or 0u64 based on the bit in each channel,
         // copying the lowest bit across the entire SIMD lane
@@ -52,37 +388,628 @@ impl RawQuadboard {
         // wwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwww
         // ^              ^               ^               ^               ^
         // └ bit 64       └ bit 48        └ bit 32        └ bit 16        └ bit 1
-        let channels = bit_channels * u64x4::splat(u64::MAX);
+        Self::from_channels([
+            bit1 * u64::MAX,
+            bit2 * u64::MAX,
+            bit3 * u64::MAX,
+            bit4 * u64::MAX,
+        ])
+    }
+
+    /// Returns the number of elements of `self` equal to each of the
+    /// sixteen nibble values, indexed by value.
+    pub const fn counts(&self) -> [u8; 16] {
+        let [c0, c1, c2, c3] = *self.as_channels();
+
+        // the masks of each combination of the lower and upper pairs of bits
+        let low = [!c1 & !c0, !c1 & c0, c1 & !c0, c1 & c0];
+        let high = [!c3 & !c2, !c3 & c2, c3 & !c2, c3 & c2];
+
+        let mut counts = [0u8; 16];
+        let mut value = 0;
+
+        while value < 16 {
+            counts[value] = (high[value >> 2] & low[value & 0b11]).count_ones() as u8;
+            value += 1;
+        }
+
+        counts
+    }
 
+    /// Returns a bitmask of the nibble values present in `self`, such that
+    /// bit `n` is set if and only if some element of `self` is equal to `n`.
+    pub fn present_values(&self) -> u16 {
+        (0..16u8).fold(0u16, |acc, value| {
+            let nibble = unsafe { Nibble::new_unchecked(value) };
+            match self.eq_mask(nibble) {
+                0 => acc,
+                _ => acc | (1 << value),
+            }
+        })
+    }
+
+    /// Returns a bitmask with a bit set at every index whose element
+    /// is equal to `value`.
+    ///
+    /// This is computed with a handful of whole-channel operations, rather
+    /// than by reading each of the 64 elements.
+    #[inline(always)]
+    pub fn eq_mask(&self, value: Nibble) -> u64 {
+        // a bit survives the XNOR in each channel iff it agrees with the
+        // corresponding bit of `value`, so the AND across all four channels
+        // is set iff the whole nibble matches
+        let matches = !(self.channels ^ Self::splat(value).channels);
+        u64x4_channel_and(matches)
+    }
+
+    /// Constructs a [`RawQuadboard`] from an array of its elements, ordered
+    /// by [`Index`].
+    ///
+    /// This is a SWAR bit-transpose, handling eight elements at a time rather
+    /// than setting each element individually.
+    #[inline(always)]
+    pub fn from_nibbles(nibbles: [Nibble; 64]) -> Self {
+        unsafe { Self::from_nibble_bytes(nibbles.map(|nibble| nibble.get())) }
+    }
+
+    /// Returns an array of the elements of `self`, ordered by [`Index`].
+    ///
+    /// This is the inverse of [`RawQuadboard::from_nibbles`], and is likewise
+    /// computed as a SWAR bit-transpose.
+    #[inline(always)]
+    pub fn to_nibbles(self) -> [Nibble; 64] {
+        self.to_nibble_bytes()
+            .map(|byte| unsafe { Nibble::new_unchecked(byte) })
+    }
+
+    /// Constructs a [`RawQuadboard`] from an array holding each element
+    /// in the lower four bits of a byte.
+    ///
+    /// This is a SWAR bit-transpose, handling eight elements at a time rather
+    /// than setting each element individually.
+    ///
+    /// # Safety
+    /// Every element of `bytes` must be strictly less than 16.
+    pub(crate) const unsafe fn from_nibble_bytes(bytes: [u8; 64]) -> Self {
+        let mut channels = [0u64; 4];
+        let mut rank = 0;
+
+        while rank < 8 {
+            let mut word = [0u8; 8];
+            let mut i = 0;
+
+            while i < 8 {
+                word[i] = bytes[8 * rank + i];
+                i += 1;
+            }
+
+            let word = u64::from_le_bytes(word);
+            let mut k = 0;
+
+            while k < 4 {
+                // gather bit k of each of the 8 bytes into a single byte
+                let bits = (word >> k) & 0x0101_0101_0101_0101;
+                let packed = bits.wrapping_mul(0x0102_0408_1020_4080) >> 56;
+                channels[k] |= packed << (8 * rank);
+                k += 1;
+            }
+
+            rank += 1;
+        }
+
+        Self::from_channels(channels)
+    }
+
+    /// Returns the elements of `self` as an array, with each element held
+    /// in the lower four bits of a byte.
+    ///
+    /// This is the inverse of [`RawQuadboard::from_nibble_bytes`].
+    pub(crate) const fn to_nibble_bytes(self) -> [u8; 64] {
+        let channels = self.as_channels();
+        let mut bytes = [0u8; 64];
+        let mut rank = 0;
+
+        while rank < 8 {
+            let mut word = 0u64;
+            let mut k = 0;
+
+            while k < 4 {
+                // spread the 8 bits of this rank out to the lowest bit of
+                // each byte: bit i is isolated in byte i, and then carried
+                // up into bit 7 of that byte by adding 0x80 - 2^i
+                let packed = (channels[k] >> (8 * rank)) & 0xFF;
+                let isolated = packed.wrapping_mul(0x0101_0101_0101_0101) & 0x8040_2010_0804_0201;
+                let spread =
+                    (isolated.wrapping_add(0x0040_6070_787C_7E7F) >> 7) & 0x0101_0101_0101_0101;
+                word |= spread << k;
+                k += 1;
+            }
+
+            let word = word.to_le_bytes();
+            let mut i = 0;
+
+            while i < 8 {
+                bytes[8 * rank + i] = word[i];
+                i += 1;
+            }
+
+            rank += 1;
+        }
+
+        bytes
+    }
+
+    /// Returns a bitmask with a bit set at every index whose element is nonzero,
+    /// i.e. the bitwise OR of the four channels.
+    #[inline(always)]
+    pub const fn occupancy(&self) -> u64 {
+        let [c0, c1, c2, c3] = *self.as_channels();
+        c0 | c1 | c2 | c3
+    }
+
+    /// Returns a bitmask with a bit set at every index whose element differs
+    /// between `self` and `other`.
+    #[inline(always)]
+    pub const fn diff_mask(&self, other: &Self) -> u64 {
+        let [a0, a1, a2, a3] = *self.as_channels();
+        let [b0, b1, b2, b3] = *other.as_channels();
+        (a0 ^ b0) | (a1 ^ b1) | (a2 ^ b2) | (a3 ^ b3)
+    }
+
+    /// Returns `true` if every element of `self` is zero.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.occupancy() == 0
+    }
+
+    /// Returns a bitmask with a bit set at every index whose element is in
+    /// `values`, where bit `n` of `values` stands for the nibble `n`.
+    #[inline(always)]
+    pub(crate) fn values_mask(&self, values: u16) -> u64 {
+        (0..16u8)
+            .filter(|value| values & (1 << value) != 0)
+            .fold(0u64, |acc, value| {
+                acc | self.eq_mask(unsafe { Nibble::new_unchecked(value) })
+            })
+    }
+
+    /// Writes `value` to every index set in `mask`, leaving the other
+    /// elements unchanged.
+    #[inline(always)]
+    pub fn fill_masked(&mut self, mask: u64, value: Nibble) {
+        *self = self.blend(mask, &Self::splat(value));
+    }
+
+    /// Adds `other` to `self` elementwise, treating each element as an
+    /// unsigned 4-bit counter and saturating at 15.
+    ///
+    /// This is computed with a ripple-carry adder across the channels, so it
+    /// costs the same handful of bitwise operations for all 64 elements.
+    pub const fn saturating_add(&self, other: &Self) -> Self {
+        let (a, b) = (self.as_channels(), other.as_channels());
+        let mut sum = [0u64; 4];
+        let mut carry = 0u64;
+        let mut k = 0;
+
+        // a ripple-carry adder, run on all 64 counters in parallel
+        while k < 4 {
+            sum[k] = a[k] ^ b[k] ^ carry;
+            carry = (a[k] & b[k]) | (carry & (a[k] ^ b[k]));
+            k += 1;
+        }
+
+        // any counter that carried out of the top bit saturates to 0b1111
+        Self::from_channels([
+            sum[0] | carry,
+            sum[1] | carry,
+            sum[2] | carry,
+            sum[3] | carry,
+        ])
+    }
+
+    /// Subtracts `other` from `self` elementwise, treating each element as
+    /// an unsigned 4-bit counter and saturating at 0.
+    ///
+    /// This is computed with a ripple-borrow subtractor across the channels,
+    /// as with [`RawQuadboard::saturating_add`].
+    pub const fn saturating_sub(&self, other: &Self) -> Self {
+        let (a, b) = (self.as_channels(), other.as_channels());
+        let mut difference = [0u64; 4];
+        let mut borrow = 0u64;
+        let mut k = 0;
+
+        while k < 4 {
+            difference[k] = a[k] ^ b[k] ^ borrow;
+            borrow = (!a[k] & b[k]) | (!(a[k] ^ b[k]) & borrow);
+            k += 1;
+        }
+
+        // any counter that borrowed out of the top bit saturates to 0
+        Self::from_channels([
+            difference[0] & !borrow,
+            difference[1] & !borrow,
+            difference[2] & !borrow,
+            difference[3] & !borrow,
+        ])
+    }
+
+    /// Rewrites every element equal to `old` to `new` in a single masked
+    /// update, and returns a bitmask of the indices that were rewritten.
+    #[inline(always)]
+    pub fn replace_all(&mut self, old: Nibble, new: Nibble) -> u64 {
+        let mask = self.eq_mask(old);
+        self.fill_masked(mask, new);
+        mask
+    }
+
+    /// Returns a new [`RawQuadboard`] taking the elements of `other` at the
+    /// indices set in `mask`, and the elements of `self` everywhere else.
+    #[inline(always)]
+    pub fn blend(&self, mask: u64, other: &Self) -> Self {
+        let mask = u64x4::splat(mask);
+        let channels = (self.channels & !mask) | (other.channels & mask);
         Self { channels }
     }
 
+    /// Returns a new [`RawQuadboard`] with every element `n` of `self`
+    /// replaced by `table[n]`.
+    ///
+    /// This is computed from the channels with one masked update for each
+    /// value present in `self`, without reading any individual elements.
+    pub fn translate(&self, table: &[Nibble; 16]) -> Self {
+        let present = self.present_values();
+        let mut board = Self::default();
+
+        for (value, &replacement) in table.iter().enumerate() {
+            if present & (1 << value) != 0 {
+                let mask = self.eq_mask(unsafe { Nibble::new_unchecked(value as u8) });
+                board.fill_masked(mask, replacement);
+            }
+        }
+
+        board
+    }
+
+    /// Applies `f` to each channel of `self`.
+    #[inline(always)]
+    fn map_channels(&self, f: impl Fn(u64) -> u64) -> Self {
+        Self::from_channels(self.as_channels().map(f))
+    }
+
+    /// Mirrors `self` vertically, such that the element at index `i`
+    /// moves to index `i ^ 56` (exchanging the first and last ranks).
+    #[inline(always)]
+    pub fn flip_vertical(&self) -> Self {
+        self.map_channels(u64::swap_bytes)
+    }
+
+    /// Mirrors `self` horizontally, such that the element at index `i`
+    /// moves to index `i ^ 7` (exchanging the first and last files).
+    #[inline(always)]
+    pub fn flip_horizontal(&self) -> Self {
+        const K1: u64 = 0x5555_5555_5555_5555;
+        const K2: u64 = 0x3333_3333_3333_3333;
+        const K4: u64 = 0x0F0F_0F0F_0F0F_0F0F;
+
+        self.map_channels(|x| {
+            let x = ((x >> 1) & K1) | ((x & K1) << 1);
+            let x = ((x >> 2) & K2) | ((x & K2) << 2);
+            ((x >> 4) & K4) | ((x & K4) << 4)
+        })
+    }
+
+    /// Rotates `self` by 180 degrees, such that the element at index `i`
+    /// moves to index `63 - i`.
+    #[inline(always)]
+    pub fn rotate_180(&self) -> Self {
+        self.map_channels(u64::reverse_bits)
+    }
+
+    /// Mirrors `self` about the diagonal through indices 0 and 63, such that
+    /// the element at rank `r` and file `f` moves to rank `f` and file `r`.
+    #[inline(always)]
+    pub fn flip_diagonal(&self) -> Self {
+        const K1: u64 = 0x5500_5500_5500_5500;
+        const K2: u64 = 0x3333_0000_3333_0000;
+        const K4: u64 = 0x0F0F_0F0F_0000_0000;
+
+        // three delta swaps, exchanging progressively smaller blocks
+        self.map_channels(|mut x| {
+            let t = K4 & (x ^ (x << 28));
+            x ^= t ^ (t >> 28);
+            let t = K2 & (x ^ (x << 14));
+            x ^= t ^ (t >> 14);
+            let t = K1 & (x ^ (x << 7));
+            x ^ t ^ (t >> 7)
+        })
+    }
+
+    /// Moves every element one square in the given direction, writing
+    /// `fill` to the squares left vacated.
+    ///
+    /// Elements which would leave the board are discarded, and in particular
+    /// never wrap around from one edge of the board to the other.
+    #[inline(always)]
+    pub fn shift(&self, direction: Direction, fill: Nibble) -> Self {
+        let mut shifted = self.map_channels(|channel| direction.shift(channel));
+        shifted.fill_masked(!direction.shift(u64::MAX), fill);
+        shifted
+    }
+
+    /// Rotates the elements of `self` by `n` indices towards [`Index::MAX`],
+    /// such that the element at index `i` moves to index `(i + n) % 64`.
+    #[inline(always)]
+    pub fn rotate_squares_left(&self, n: u32) -> Self {
+        self.map_channels(|channel| channel.rotate_left(n))
+    }
+
+    /// Rotates the elements of `self` by `n` indices towards [`Index::MIN`],
+    /// such that the element at index `i` moves to index `(i - n) % 64`.
+    #[inline(always)]
+    pub fn rotate_squares_right(&self, n: u32) -> Self {
+        self.map_channels(|channel| channel.rotate_right(n))
+    }
+
+    /// Moves the elements of `self` by `n` indices towards [`Index::MAX`],
+    /// discarding the elements which pass it and filling the lowest `n`
+    /// indices with zeroes.
+    ///
+    /// Unlike [`u64::shl`](core::ops::Shl), this is well-defined for any
+    /// `n`, and returns an empty board when `n` is at least 64.
+    #[inline(always)]
+    pub fn shift_squares_left(&self, n: u32) -> Self {
+        self.map_channels(|channel| channel.checked_shl(n).unwrap_or(0))
+    }
+
+    /// Moves the elements of `self` by `n` indices towards [`Index::MIN`],
+    /// discarding the elements which pass it and filling the highest `n`
+    /// indices with zeroes.
+    ///
+    /// Unlike [`u64::shr`](core::ops::Shr), this is well-defined for any
+    /// `n`, and returns an empty board when `n` is at least 64.
+    #[inline(always)]
+    pub fn shift_squares_right(&self, n: u32) -> Self {
+        self.map_channels(|channel| channel.checked_shr(n).unwrap_or(0))
+    }
+
+    /// Moves every element one square north, filling the first rank with zeroes.
+    #[inline(always)]
+    pub fn shift_north(&self) -> Self {
+        self.shift(Direction::North, Nibble::ZERO)
+    }
+
+    /// Moves every element one square north-east, filling the first rank
+    /// and the a-file with zeroes.
+    #[inline(always)]
+    pub fn shift_north_east(&self) -> Self {
+        self.shift(Direction::NorthEast, Nibble::ZERO)
+    }
+
+    /// Moves every element one square east, filling the a-file with zeroes.
+    #[inline(always)]
+    pub fn shift_east(&self) -> Self {
+        self.shift(Direction::East, Nibble::ZERO)
+    }
+
+    /// Moves every element one square south-east, filling the eighth rank
+    /// and the a-file with zeroes.
+    #[inline(always)]
+    pub fn shift_south_east(&self) -> Self {
+        self.shift(Direction::SouthEast, Nibble::ZERO)
+    }
+
+    /// Moves every element one square south, filling the eighth rank with zeroes.
+    #[inline(always)]
+    pub fn shift_south(&self) -> Self {
+        self.shift(Direction::South, Nibble::ZERO)
+    }
+
+    /// Moves every element one square south-west, filling the eighth rank
+    /// and the h-file with zeroes.
+    #[inline(always)]
+    pub fn shift_south_west(&self) -> Self {
+        self.shift(Direction::SouthWest, Nibble::ZERO)
+    }
+
+    /// Moves every element one square west, filling the h-file with zeroes.
+    #[inline(always)]
+    pub fn shift_west(&self) -> Self {
+        self.shift(Direction::West, Nibble::ZERO)
+    }
+
+    /// Moves every element one square north-west, filling the first rank
+    /// and the h-file with zeroes.
+    #[inline(always)]
+    pub fn shift_north_west(&self) -> Self {
+        self.shift(Direction::NorthWest, Nibble::ZERO)
+    }
+
     /// Returns the [`Nibble`] at `index`.
     #[inline(always)]
-    pub fn get(&self, index: Index) -> Nibble {
+    pub const fn get(&self, index: Index) -> Nibble {
         unsafe { self.get_unchecked(index.get()) }
     }
 
     /// Sets the value of `self` at `index` to `value`.
     #[inline(always)]
-    pub fn set(&mut self, index: Index, value: Nibble) {
+    pub const fn set(&mut self, index: Index, value: Nibble) {
         unsafe { self.set_unchecked(index.get(), value) }
     }
 
+    /// Returns the [`Nibble`]s at each of `indices`, in the same order.
+    ///
+    /// The channels are read once and shared between all the lookups, which
+    /// is cheaper than calling [`RawQuadboard::get`] for each index.
+    #[inline(always)]
+    pub fn get_many<const N: usize>(&self, indices: [Index; N]) -> [Nibble; N] {
+        let [c0, c1, c2, c3] = self.into_channels();
+
+        indices.map(|index| {
+            let i = index.get();
+            let value = ((c0 >> i) & 1)
+                | (((c1 >> i) & 1) << 1)
+                | (((c2 >> i) & 1) << 2)
+                | (((c3 >> i) & 1) << 3);
+            unsafe { Nibble::new_unchecked(value as u8) }
+        })
+    }
+
+    /// Applies each of `writes` in order, so that later writes to an index
+    /// take precedence over earlier ones.
+    ///
+    /// The writes are combined into a single masked update of the channels,
+    /// which is cheaper than calling [`RawQuadboard::set`] for each of them.
+    #[inline(always)]
+    pub fn set_many(&mut self, writes: &[(Index, Nibble)]) {
+        self.set_all(writes.iter().copied());
+    }
+
+    /// Applies each of `writes` in order as a single masked update.
+    #[inline(always)]
+    pub(crate) fn set_all(&mut self, writes: impl IntoIterator<Item = (Index, Nibble)>) {
+        let mut mask = 0u64;
+        let mut values = [0u64; 4];
+
+        for (index, value) in writes {
+            let bit = 1 << index.get();
+            mask |= bit;
+
+            for (n, channel) in values.iter_mut().enumerate() {
+                let set = ((value.get() >> n) & 1) as u64;
+                *channel = (*channel & !bit) | (set << index.get());
+            }
+        }
+
+        self.channels &= u64x4::splat(!mask);
+        self.channels |= u64x4::from_array(values);
+    }
+
+    /// Returns the eight [`Nibble`]s in `rank`, in order of increasing file.
+    ///
+    /// Each channel stores a rank as one of its bytes, so this reads a
+    /// single byte from each channel rather than eight separate indices.
+    #[inline(always)]
+    pub fn rank(&self, rank: Rank) -> [Nibble; 8] {
+        let shift = 8 * rank.get();
+        Self::unpack_row(self.into_channels().map(|channel| (channel >> shift) as u8))
+    }
+
+    /// Overwrites the eight elements in `rank` with `values`, in order of
+    /// increasing file.
+    #[inline(always)]
+    pub fn set_rank(&mut self, rank: Rank, values: [Nibble; 8]) {
+        let shift = 8 * rank.get();
+        let rows = Self::pack_row(values);
+
+        for (channel, row) in self.as_channels_mut().iter_mut().zip(rows) {
+            *channel = (*channel & !rank.mask()) | ((row as u64) << shift);
+        }
+    }
+
+    /// Returns the eight [`Nibble`]s in `file`, in order of increasing rank.
+    ///
+    /// The file bits of each channel are gathered into a single byte with a
+    /// multiplication, rather than reading eight separate indices.
+    #[inline(always)]
+    pub fn file(&self, file: File) -> [Nibble; 8] {
+        let shift = file.get();
+
+        Self::unpack_row(self.into_channels().map(|channel| {
+            // the multiplier moves the bit at 8k to bit 56 + k, and the
+            // partial products never overlap, so no carries are produced
+            let column = (channel >> shift) & File::A.mask();
+            (column.wrapping_mul(0x0102_0408_1020_4080) >> 56) as u8
+        }))
+    }
+
+    /// Overwrites the eight elements in `file` with `values`, in order of
+    /// increasing rank.
+    #[inline(always)]
+    pub fn set_file(&mut self, file: File, values: [Nibble; 8]) {
+        let shift = file.get();
+        let rows = Self::pack_row(values);
+
+        for (channel, row) in self.as_channels_mut().iter_mut().zip(rows) {
+            // copying the row into every byte and keeping only bit k of byte
+            // k leaves each byte either zero or a single bit below 0x80, so
+            // adding 0x7F to every byte sets its high bit iff it is nonzero
+            let diagonal = (row as u64 * 0x0101_0101_0101_0101) & 0x8040_2010_0804_0201;
+            let column = ((diagonal + 0x7F7F_7F7F_7F7F_7F7F) >> 7) & File::A.mask();
+            *channel = (*channel & !file.mask()) | (column << shift);
+        }
+    }
+
+    /// Transposes one byte from each channel into eight [`Nibble`]s, where
+    /// bit `k` of each byte belongs to the `k`th nibble.
+    #[inline(always)]
+    fn unpack_row(rows: [u8; 4]) -> [Nibble; 8] {
+        core::array::from_fn(|k| {
+            let value = rows
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (n, row)| acc | (((row >> k) & 1) << n));
+            unsafe { Nibble::new_unchecked(value) }
+        })
+    }
+
+    /// Transposes eight [`Nibble`]s into one byte for each channel; this is
+    /// the inverse of [`RawQuadboard::unpack_row`].
+    #[inline(always)]
+    fn pack_row(values: [Nibble; 8]) -> [u8; 4] {
+        core::array::from_fn(|n| {
+            values
+                .iter()
+                .enumerate()
+                .fold(0, |acc, (k, value)| acc | (((value.get() >> n) & 1) << k))
+        })
+    }
+
+    /// Exchanges the elements at `a` and `b`.
+    #[inline(always)]
+    pub fn swap(&mut self, a: Index, b: Index) {
+        let (a, b) = (a.get() as u64, b.get() as u64);
+
+        // a lane is 1 iff the two elements differ in that channel, in which
+        // case both of their bits are flipped
+        let differing = ((self.channels >> a) ^ (self.channels >> b)) & u64x4::splat(1);
+        self.channels ^= differing * u64x4::splat((1 << a) | (1 << b));
+    }
+
+    /// Moves the element at `from` to `to`, overwriting the element at `to`
+    /// and leaving `fill` in its place at `from`.
+    ///
+    /// This is equivalent to reading the element at `from`, writing `fill`
+    /// to `from` and then writing the original element to `to`, so it does
+    /// nothing when `from == to`.
+    #[inline(always)]
+    pub fn relocate(&mut self, from: Index, to: Index, fill: Nibble) {
+        let (from, to) = (from.get() as u64, to.get() as u64);
+        let (bit1, bit2, bit3, bit4) = unsafe { lower_nibble_bits(fill.get()) };
+
+        let moved = ((self.channels >> from) & u64x4::splat(1)) << to;
+        let filled =
+            (u64x4::from_array([bit1, bit2, bit3, bit4]) << from) & u64x4::splat(!(1 << to));
+
+        self.channels &= u64x4::splat(!((1 << from) | (1 << to)));
+        self.channels |= moved | filled;
+    }
+
     /// Returns the [`Nibble`] at `index` without bounds checking.
     ///
     /// # Safety
     /// `index` must be strictly less than 64.
     #[inline(always)]
-    pub unsafe fn get_unchecked(&self, index: u8) -> Nibble {
-        // mask off all other values and shift the remaining bits right
-        let mask = u64x4::splat(1 << index);
-        let masked_board = self.channels & mask;
-        let bits = masked_board >> (index as u64);
+    pub const unsafe fn get_unchecked(&self, index: u8) -> Nibble {
+        let [c0, c1, c2, c3] = *self.as_channels();
+
+        // shift the indexed bit of each channel down to the bottom,
+        // and then up according to the channel index
+        let bit1 = (c0 >> index) & 1;
+        let bit2 = ((c1 >> index) & 1) << 1;
+        let bit3 = ((c2 >> index) & 1) << 2;
+        let bit4 = ((c3 >> index) & 1) << 3;
 
-        // shift values according to channel index
-        let values = bits << u64x4::from_array([0, 1, 2, 3]);
-        unsafe { Nibble::new_unchecked(u64x4_channel_sum(values) as u8) }
+        unsafe { Nibble::new_unchecked((bit1 | bit2 | bit3 | bit4) as u8) }
     }
 
     /// Writes `value` to `index` without bounds checking.
@@ -90,34 +1017,45 @@ impl RawQuadboard {
     /// # Safety
     /// `index` must be strictly less than 64.
     #[inline(always)]
-    pub unsafe fn set_unchecked(&mut self, index: u8, value: Nibble) {
-        let value: u8 = value.get();
-        let (bit1, bit2, bit3, bit4) = unsafe { lower_nibble_bits(value) };
+    pub const unsafe fn set_unchecked(&mut self, index: u8, value: Nibble) {
+        let [bit1, bit2, bit3, bit4] = NIBBLE_BITS[value.get() as usize];
+        let [c0, c1, c2, c3] = self.into_channels();
 
-        // shift the bits to the indexed location
-        let channel1 = bit1 << index;
-        let channel2 = bit2 << index;
-        let channel3 = bit3 << index;
-        let channel4 = bit4 << index;
-
-        // create mask vector with all bits set and clear the bits at the indexed location
-        let mut mask = u64x4::splat(u64::MAX);
+        // mask off the existing value and write the new value's bits
+        // to the indexed location; looking the bits up rather than
+        // extracting them keeps this off the critical path of the write
         let clear_mask = !(1 << index);
-        mask &= u64x4::splat(clear_mask);
-
-        // mask off existing value and write new value
-        self.channels &= mask;
-        self.channels |= u64x4::from_array([channel1, channel2, channel3, channel4]);
+        *self = Self::from_channels([
+            (c0 & clear_mask) | (bit1 << index),
+            (c1 & clear_mask) | (bit2 << index),
+            (c2 & clear_mask) | (bit3 << index),
+            (c3 & clear_mask) | (bit4 << index),
+        ]);
     }
 }
 
-/// A `const` equivalent to `value.to_array().iter().sum()`.
+/// A `const` equivalent to `value.reduce_and()`.
 #[inline(always)]
-const fn u64x4_channel_sum(value: u64x4) -> u64 {
+const fn u64x4_channel_and(value: u64x4) -> u64 {
     let arr = value.to_array();
-    arr[0] + arr[1] + arr[2] + arr[3]
+    arr[0] & arr[1] & arr[2] & arr[3]
 }
 
+/// The bits of each nibble as returned by [`lower_nibble_bits`], indexed by
+/// the value of the nibble.
+const NIBBLE_BITS: [[u64; 4]; 16] = {
+    let mut table = [[0; 4]; 16];
+    let mut value = 0;
+
+    while value < 16 {
+        let (bit1, bit2, bit3, bit4) = unsafe { lower_nibble_bits(value as u8) };
+        table[value] = [bit1, bit2, bit3, bit4];
+        value += 1;
+    }
+
+    table
+};
+
 /// Extracts the lower 4 bits from the given value
 /// and returns them in increasing order from left
 /// to right.
@@ -171,6 +1109,483 @@ mod tests {
         assert_eq!(channels[3], 0x0000000100000021);
     }
 
+    #[test]
+    fn raw_quadboard_layout_is_four_aligned_channels() {
+        assert_eq!(std::mem::size_of::<RawQuadboard>(), 32);
+        assert_eq!(std::mem::align_of::<RawQuadboard>(), 32);
+
+        let mut rqb = RawQuadboard::default();
+        rqb.set(1.try_into().unwrap(), Nibble::try_from(0b1010).unwrap());
+
+        let channels: [u64; 4] = unsafe { std::mem::transmute(rqb) };
+        assert_eq!(channels, [0, 0b10, 0, 0b10]);
+    }
+
+    #[test]
+    fn raw_quadboard_eq_mask_is_correct() {
+        let mut rqb = RawQuadboard::splat(Nibble::SIX);
+        rqb.set(0.try_into().unwrap(), Nibble::SEVEN);
+        rqb.set(63.try_into().unwrap(), Nibble::SEVEN);
+
+        assert_eq!(rqb.eq_mask(Nibble::SEVEN), (1 << 63) | 1);
+        assert_eq!(rqb.eq_mask(Nibble::SIX), !((1 << 63) | 1));
+        assert_eq!(rqb.eq_mask(Nibble::FIVE), 0);
+    }
+
+    #[test]
+    fn raw_quadboard_occupancy_is_correct() {
+        let mut rqb = RawQuadboard::default();
+        assert!(rqb.is_empty());
+
+        rqb.set(4.try_into().unwrap(), Nibble::EIGHT);
+        rqb.set(50.try_into().unwrap(), Nibble::ONE);
+        assert_eq!(rqb.occupancy(), (1 << 50) | (1 << 4));
+        assert!(!rqb.is_empty());
+    }
+
+    #[test]
+    fn raw_quadboard_counts_is_correct() {
+        let bytes: [u8; 64] = std::array::from_fn(|i| (i / 4) as u8);
+        let rqb = unsafe { RawQuadboard::from_nibble_bytes(bytes) };
+        assert_eq!(rqb.counts(), [4; 16]);
+
+        let mut rqb = RawQuadboard::splat(Nibble::NINE);
+        rqb.set(3.try_into().unwrap(), Nibble::TWO);
+        let counts = rqb.counts();
+        assert_eq!((counts[9], counts[2], counts[0]), (63, 1, 0));
+    }
+
+    #[test]
+    fn raw_quadboard_transforms_permute_indices() {
+        let bytes: [u8; 64] = std::array::from_fn(|i| (i % 16) as u8);
+        let rqb = unsafe { RawQuadboard::from_nibble_bytes(bytes) };
+
+        let vertical = rqb.flip_vertical().to_nibble_bytes();
+        let horizontal = rqb.flip_horizontal().to_nibble_bytes();
+        let rotated = rqb.rotate_180().to_nibble_bytes();
+        let diagonal = rqb.flip_diagonal().to_nibble_bytes();
+
+        for i in 0..64 {
+            let (rank, file) = (i / 8, i % 8);
+            assert_eq!(vertical[i ^ 56], bytes[i]);
+            assert_eq!(horizontal[i ^ 7], bytes[i]);
+            assert_eq!(rotated[63 - i], bytes[i]);
+            assert_eq!(diagonal[8 * file + rank], bytes[i]);
+        }
+    }
+
+    #[test]
+    fn raw_quadboard_shift_moves_and_fills() {
+        let mut rqb = RawQuadboard::default();
+        rqb.set(7.try_into().unwrap(), Nibble::FIVE);
+        rqb.set(8.try_into().unwrap(), Nibble::SIX);
+
+        let east = rqb.shift(Direction::East, Nibble::ONE);
+        assert_eq!(east.eq_mask(Nibble::SIX), 1 << 9);
+        assert_eq!(east.eq_mask(Nibble::FIVE), 0);
+        assert_eq!(east.eq_mask(Nibble::ONE), 0x0101_0101_0101_0101);
+
+        let north = rqb.shift_north();
+        assert_eq!(north.eq_mask(Nibble::FIVE), 1 << 15);
+        assert_eq!(north.eq_mask(Nibble::SIX), 1 << 16);
+        assert_eq!(north.shift_south(), rqb);
+    }
+
+    #[test]
+    #[cfg(feature = "simd")]
+    fn raw_quadboard_simd_access_matches_channels() {
+        let channels = [1, 1 << 7, u64::MAX, 0];
+        let mut rqb = RawQuadboard::from_simd(core::simd::u64x4::from_array(channels));
+        assert_eq!(rqb.into_channels(), channels);
+        assert_eq!(rqb.as_simd().to_array(), channels);
+        assert_eq!(rqb.get(Index::MIN), Nibble::FIVE);
+
+        *rqb.as_simd_mut() ^= core::simd::u64x4::splat(1);
+        assert_eq!(rqb.get(Index::MIN), Nibble::TEN);
+        assert_eq!(rqb.into_simd(), rqb.channels);
+    }
+
+    #[test]
+    fn raw_quadboard_square_rotations_and_shifts_are_correct() {
+        let bytes: [u8; 64] = core::array::from_fn(|i| (i % 16) as u8);
+        let rqb = unsafe { RawQuadboard::from_nibble_bytes(bytes) };
+
+        let left = rqb.rotate_squares_left(5).to_nibble_bytes();
+        assert!((0..64).all(|i| left[(i + 5) % 64] == bytes[i]));
+        assert_eq!(rqb.rotate_squares_left(5).rotate_squares_right(5), rqb);
+        assert_eq!(rqb.rotate_squares_left(64), rqb);
+
+        let shifted = rqb.shift_squares_left(10);
+        assert_eq!(shifted.occupancy() & 0x3FF, 0);
+        assert_eq!(shifted.get(Index::try_from(20).unwrap()), Nibble::TEN);
+        assert_eq!(
+            rqb.shift_squares_right(60).to_nibble_bytes()[..4],
+            bytes[60..]
+        );
+        assert_eq!(rqb.shift_squares_right(64), RawQuadboard::default());
+        assert_eq!(rqb.shift_squares_left(200), RawQuadboard::default());
+    }
+
+    #[test]
+    fn raw_quadboard_swap_and_relocate_are_correct() {
+        let (a, b, c) = (
+            3.try_into().unwrap(),
+            60.try_into().unwrap(),
+            17.try_into().unwrap(),
+        );
+        let mut rqb = RawQuadboard::default();
+        rqb.set(a, Nibble::NINE);
+        rqb.set(b, Nibble::SIX);
+
+        rqb.swap(a, b);
+        assert_eq!(rqb.get(a), Nibble::SIX);
+        assert_eq!(rqb.get(b), Nibble::NINE);
+        rqb.swap(a, a);
+        assert_eq!(rqb.get(a), Nibble::SIX);
+
+        rqb.relocate(b, c, Nibble::TWO);
+        assert_eq!(rqb.get(b), Nibble::TWO);
+        assert_eq!(rqb.get(c), Nibble::NINE);
+        rqb.relocate(a, b, Nibble::ZERO);
+        assert_eq!(rqb.get(a), Nibble::ZERO);
+        assert_eq!(rqb.get(b), Nibble::SIX);
+        rqb.relocate(c, c, Nibble::FIFTEEN);
+        assert_eq!(rqb.get(c), Nibble::NINE);
+        assert_eq!(rqb.present_values(), 0b0000_0010_0100_0001);
+    }
+
+    #[test]
+    fn raw_quadboard_diff_mask_is_correct() {
+        let rqb = RawQuadboard::splat(Nibble::SEVEN);
+        assert_eq!(rqb.diff_mask(&rqb), 0);
+
+        let mut other = rqb;
+        other.set(4.try_into().unwrap(), Nibble::FIFTEEN);
+        other.set(40.try_into().unwrap(), Nibble::ZERO);
+        assert_eq!(rqb.diff_mask(&other), (1 << 4) | (1 << 40));
+        assert_eq!(other.diff_mask(&rqb), (1 << 4) | (1 << 40));
+    }
+
+    #[test]
+    fn raw_quadboard_hash_agrees_with_eq() {
+        use std::collections::HashSet;
+
+        let mut other = RawQuadboard::default();
+        other.set(9.try_into().unwrap(), Nibble::ONE);
+
+        let set: HashSet<_> = [RawQuadboard::default(), other, RawQuadboard::default()].into();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&other));
+    }
+
+    #[test]
+    fn raw_quadboard_fill_masked_only_writes_masked_indices() {
+        let mut rqb = RawQuadboard::splat(Nibble::TEN);
+        rqb.fill_masked(0xFF00_0000_0000_00FF, Nibble::THREE);
+
+        assert_eq!(rqb.eq_mask(Nibble::THREE), 0xFF00_0000_0000_00FF);
+        assert_eq!(rqb.eq_mask(Nibble::TEN), 0x00FF_FFFF_FFFF_FF00);
+    }
+
+    #[test]
+    fn raw_quadboard_blend_selects_by_mask() {
+        let low = RawQuadboard::splat(Nibble::ONE);
+        let high = RawQuadboard::splat(Nibble::FOURTEEN);
+        let blended = low.blend(0xAAAA_AAAA_AAAA_AAAA, &high);
+
+        assert_eq!(blended.eq_mask(Nibble::FOURTEEN), 0xAAAA_AAAA_AAAA_AAAA);
+        assert_eq!(blended.eq_mask(Nibble::ONE), 0x5555_5555_5555_5555);
+        assert_eq!(low.blend(0, &high), low);
+        assert_eq!(low.blend(u64::MAX, &high), high);
+    }
+
+    #[test]
+    fn raw_quadboard_channels_can_be_written_directly() {
+        const BOARD: RawQuadboard = RawQuadboard::from_channels([1, 0, 0, 1 << 63]);
+        assert_eq!(BOARD.get(0.try_into().unwrap()), Nibble::ONE);
+        assert_eq!(BOARD.get(63.try_into().unwrap()), Nibble::EIGHT);
+
+        let mut rqb = BOARD;
+        rqb.set_channel(1, 1);
+        rqb.as_channels_mut()[2] = 1 << 63;
+        assert_eq!(rqb.into_channels(), [1, 1, 1 << 63, 1 << 63]);
+        assert_eq!(rqb.get(0.try_into().unwrap()), Nibble::THREE);
+        assert_eq!(rqb.get(63.try_into().unwrap()), Nibble::TWELVE);
+    }
+
+    #[test]
+    #[should_panic]
+    fn raw_quadboard_set_channel_panics_out_of_range() {
+        RawQuadboard::default().set_channel(4, 0);
+    }
+
+    #[test]
+    fn raw_quadboard_named_channels_match_nibble_bits() {
+        let index = 20.try_into().unwrap();
+        let mut rqb = RawQuadboard::default();
+        rqb.set(index, Nibble::NINE);
+
+        for channel in Channel::ALL {
+            let expected = match Nibble::NINE.get() & channel.weight() {
+                0 => 0,
+                _ => 1 << 20,
+            };
+            assert_eq!(rqb.channel(channel), expected);
+        }
+
+        let rqb = rqb.with_channel(Channel::Fours, 1 << 20);
+        assert_eq!(rqb.get(index), Nibble::THIRTEEN);
+        assert_eq!(rqb.channel(Channel::Fours), 1 << 20);
+    }
+
+    #[test]
+    fn raw_quadboard_get_many_agrees_with_get() {
+        let bytes: [u8; 64] = core::array::from_fn(|i| ((i * 5) % 16) as u8);
+        let rqb = unsafe { RawQuadboard::from_nibble_bytes(bytes) };
+        let indices = [0, 63, 17, 17, 42].map(|i: u8| Index::try_from(i).unwrap());
+
+        assert_eq!(rqb.get_many(indices), indices.map(|index| rqb.get(index)));
+        assert_eq!(rqb.get_many([]), []);
+    }
+
+    #[test]
+    fn raw_quadboard_set_many_applies_writes_in_order() {
+        let (a, b) = (Index::try_from(8).unwrap(), Index::try_from(50).unwrap());
+        let mut rqb = RawQuadboard::splat(Nibble::FIFTEEN);
+        rqb.set_many(&[(a, Nibble::SIX), (b, Nibble::NINE), (a, Nibble::ONE)]);
+
+        assert_eq!(rqb.get(a), Nibble::ONE);
+        assert_eq!(rqb.get(b), Nibble::NINE);
+        assert_eq!(rqb.eq_mask(Nibble::FIFTEEN), !((1 << 8) | (1 << 50)));
+
+        rqb.set_many(&[]);
+        assert_eq!(rqb.get(a), Nibble::ONE);
+    }
+
+    #[test]
+    fn raw_quadboard_ranks_and_files_agree_with_get() {
+        let bytes: [u8; 64] = core::array::from_fn(|i| ((i * 7 + i / 8) % 16) as u8);
+        let rqb = unsafe { RawQuadboard::from_nibble_bytes(bytes) };
+
+        for (rank, file) in Rank::ALL.into_iter().zip(File::ALL) {
+            assert!(rank
+                .squares()
+                .map(|index| rqb.get(index))
+                .eq(rqb.rank(rank)));
+            assert!(file
+                .squares()
+                .map(|index| rqb.get(index))
+                .eq(rqb.file(file)));
+        }
+
+        let values: [Nibble; 8] = core::array::from_fn(|k| Nibble::try_from(15 - k as u8).unwrap());
+        let mut ranked = rqb;
+        ranked.set_rank(Rank::Third, values);
+        assert_eq!(ranked.rank(Rank::Third), values);
+        assert_eq!(ranked.diff_mask(&rqb) & !Rank::Third.mask(), 0);
+
+        let mut filed = rqb;
+        filed.set_file(File::G, values);
+        assert_eq!(filed.file(File::G), values);
+        assert_eq!(filed.diff_mask(&rqb) & !File::G.mask(), 0);
+        filed.set_file(File::G, [Nibble::ZERO; 8]);
+        assert_eq!(filed.file(File::G), [Nibble::ZERO; 8]);
+    }
+
+    #[test]
+    fn raw_quadboard_nibble_arrays_round_trip() {
+        let nibbles: [Nibble; 64] =
+            core::array::from_fn(|i| Nibble::try_from(((i * 11) % 16) as u8).unwrap());
+        let rqb = RawQuadboard::from_nibbles(nibbles);
+
+        for index in Index::all() {
+            assert_eq!(rqb.get(index), nibbles[index.get() as usize]);
+        }
+
+        assert_eq!(rqb.to_nibbles(), nibbles);
+    }
+
+    #[test]
+    fn raw_quadboard_random_draws_one_word_per_channel() {
+        let mut words = [1u64, 2, 4, 8].into_iter();
+        let rqb = RawQuadboard::random(&mut || words.next().unwrap());
+
+        assert_eq!(rqb.get(Index::try_from(0).unwrap()), Nibble::ONE);
+        assert_eq!(rqb.get(Index::try_from(3).unwrap()), Nibble::EIGHT);
+        assert_eq!(words.next(), None);
+    }
+
+    #[test]
+    fn raw_quadboard_core_operations_are_const() {
+        const BOARD: RawQuadboard = {
+            let mut board = RawQuadboard::splat(Nibble::SEVEN);
+            board.set(Index::MIN, Nibble::TWELVE);
+            board
+        };
+        const CORNER: Nibble = BOARD.get(Index::MIN);
+
+        assert_eq!(CORNER, Nibble::TWELVE);
+        assert_eq!(BOARD.get(Index::MAX), Nibble::SEVEN);
+        assert_eq!(BOARD.eq_mask(Nibble::SEVEN), !1);
+    }
+
+    #[test]
+    fn raw_quadboard_translate_applies_table() {
+        let bytes: [u8; 64] = core::array::from_fn(|i| (i % 16) as u8);
+        let rqb = unsafe { RawQuadboard::from_nibble_bytes(bytes) };
+        let table: [Nibble; 16] =
+            core::array::from_fn(|n| Nibble::try_from((15 - n) as u8).unwrap());
+
+        let translated = rqb.translate(&table);
+        assert_eq!(translated.to_nibble_bytes(), bytes.map(|byte| 15 - byte));
+        assert_eq!(translated.translate(&table), rqb);
+    }
+
+    #[test]
+    fn raw_quadboard_replace_all_rewrites_matches() {
+        let mut rqb = RawQuadboard::splat(Nibble::TWO);
+        rqb.set(Index::MIN, Nibble::THREE);
+
+        assert_eq!(rqb.replace_all(Nibble::TWO, Nibble::FOUR), !1);
+        assert_eq!(rqb.eq_mask(Nibble::FOUR), !1);
+        assert_eq!(rqb.get(Index::MIN), Nibble::THREE);
+        assert_eq!(rqb.replace_all(Nibble::TWO, Nibble::ONE), 0);
+    }
+
+    #[test]
+    fn raw_quadboard_saturating_arithmetic_is_elementwise() {
+        let a =
+            unsafe { RawQuadboard::from_nibble_bytes(core::array::from_fn(|i| (i % 16) as u8)) };
+        let b = unsafe {
+            RawQuadboard::from_nibble_bytes(core::array::from_fn(|i| ((i / 4) % 16) as u8))
+        };
+        let (sum, difference) = (a.saturating_add(&b), a.saturating_sub(&b));
+
+        for index in Index::all() {
+            let (x, y) = (a.get(index).get(), b.get(index).get());
+            assert_eq!(sum.get(index).get(), (x + y).min(15));
+            assert_eq!(difference.get(index).get(), x.saturating_sub(y));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn raw_quadboard_compact_encoding_round_trips() {
+        let mut rqb = RawQuadboard::default();
+        for (i, value) in [(0, Nibble::ONE), (9, Nibble::FIFTEEN), (63, Nibble::SIX)] {
+            rqb.set(Index::try_from(i).unwrap(), value);
+        }
+
+        let mut bytes = Vec::new();
+        rqb.encode_compact(&mut bytes);
+        RawQuadboard::splat(Nibble::TWO).encode_compact(&mut bytes);
+        assert_eq!(bytes.len(), rqb.compact_len() + 40);
+        assert_eq!(&bytes[8..10], &[0xF1, 0x06]);
+
+        let (decoded, rest) = RawQuadboard::decode_compact(&bytes).unwrap();
+        assert_eq!(decoded, rqb);
+        let (decoded, rest) = RawQuadboard::decode_compact(rest).unwrap();
+        assert_eq!(decoded, RawQuadboard::splat(Nibble::TWO));
+        assert!(rest.is_empty());
+
+        let empty = RawQuadboard::default();
+        assert_eq!(empty.compact_len(), 8);
+
+        // the encoding is shorter than 32 bytes for at most 46 nonzero elements
+        let ones = RawQuadboard::splat(Nibble::ONE);
+        for (nonzero, len) in [(46, 31), (47, 32), (48, 32)] {
+            let board = RawQuadboard::default().blend((1 << nonzero) - 1, &ones);
+            assert_eq!(board.compact_len(), len);
+        }
+        assert_eq!(RawQuadboard::decode_compact(&[0; 8]), Ok((empty, &[][..])));
+    }
+
+    #[test]
+    fn raw_quadboard_compact_encoding_rejects_invalid_input() {
+        let one = 1u64.to_le_bytes();
+        let three = 0b111u64.to_le_bytes();
+
+        assert_eq!(
+            RawQuadboard::decode_compact(&one[..7]),
+            Err(InvalidCompactEncodingError::Truncated)
+        );
+        assert_eq!(
+            RawQuadboard::decode_compact(&one),
+            Err(InvalidCompactEncodingError::Truncated)
+        );
+        assert_eq!(
+            RawQuadboard::decode_compact(&[&one[..], &[0x10]].concat()),
+            Err(InvalidCompactEncodingError::NonCanonical)
+        );
+        assert_eq!(
+            RawQuadboard::decode_compact(&[&three[..], &[0x21, 0x03]].concat())
+                .map(|(board, _)| board.occupancy()),
+            Ok(0b111)
+        );
+        assert_eq!(
+            RawQuadboard::decode_compact(&[&three[..], &[0x21, 0x00]].concat()),
+            Err(InvalidCompactEncodingError::NonCanonical)
+        );
+    }
+
+    #[test]
+    fn raw_quadboard_present_values_is_correct() {
+        let mut rqb = RawQuadboard::splat(Nibble::THREE);
+        assert_eq!(rqb.present_values(), 0b1000);
+
+        rqb.set(10.try_into().unwrap(), Nibble::FIFTEEN);
+        rqb.set(11.try_into().unwrap(), Nibble::ZERO);
+        assert_eq!(rqb.present_values(), 0b1000_0000_0000_1001);
+    }
+
+    #[test]
+    fn raw_quadboard_byte_encodings_are_canonical() {
+        let mut rqb = RawQuadboard::default();
+        rqb.set(0.try_into().unwrap(), Nibble::try_from(0b0011).unwrap());
+
+        let le = rqb.to_le_bytes();
+        let be = rqb.to_be_bytes();
+
+        // channels 0 and 1 hold the lowest bit, stored first in
+        // little-endian order and last in big-endian order
+        assert_eq!(le[0], 1);
+        assert_eq!(le[8], 1);
+        assert_eq!(be[7], 1);
+        assert_eq!(be[15], 1);
+
+        assert_eq!(RawQuadboard::from_le_bytes(le), rqb);
+        assert_eq!(RawQuadboard::from_be_bytes(be), rqb);
+    }
+
+    #[test]
+    fn raw_quadboard_le_bytes_follow_documented_layout() {
+        for i in 0..64u8 {
+            let mut rqb = RawQuadboard::default();
+            rqb.set(i.try_into().unwrap(), Nibble::FIFTEEN);
+            let bytes = rqb.to_le_bytes();
+
+            for (k, &byte) in bytes.iter().enumerate() {
+                let expected = match k % 8 == (i / 8) as usize {
+                    true => 1 << (i % 8),
+                    false => 0,
+                };
+                assert_eq!(byte, expected, "byte {k} for index {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn raw_quadboard_nibble_byte_transpose_is_correct() {
+        let bytes: [u8; 64] = std::array::from_fn(|i| ((i * 7) % 16) as u8);
+        let rqb = unsafe { RawQuadboard::from_nibble_bytes(bytes) };
+
+        for (i, &byte) in bytes.iter().enumerate() {
+            assert_eq!(unsafe { rqb.get_unchecked(i as u8) }.get(), byte);
+        }
+
+        assert_eq!(rqb.to_nibble_bytes(), bytes);
+    }
+
     #[test]
     fn raw_quadboard_get_unchecked_is_correct() {
         let mut rqb = RawQuadboard::default();
@@ -0,0 +1,241 @@
+//! Rendering typed boards as PNG diagrams.
+//!
+//! The encoder here is deliberately minimal: images are written as 8-bit RGB
+//! with uncompressed deflate blocks, which keeps the implementation free of
+//! dependencies at the cost of somewhat larger files. Squares are laid out
+//! with index 0 in the bottom-left corner and index 63 in the top-right, so
+//! that a chessboard appears from white's perspective.
+
+use halfling::Nibble;
+
+use crate::checksum::crc32;
+use crate::Quadboard;
+
+/// The colours and dimensions used to render a board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// The colour of the light squares, as RGB.
+    pub light_square: [u8; 3],
+    /// The colour of the dark squares, as RGB.
+    pub dark_square: [u8; 3],
+    /// The side length of each square, in pixels.
+    pub square_size: u32,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            light_square: [0xEE, 0xEE, 0xD2],
+            dark_square: [0x76, 0x96, 0x56],
+            square_size: 16,
+        }
+    }
+}
+
+/// The error produced when a [`Theme`] describes an image which cannot be
+/// rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidThemeError {
+    /// The square size was zero, which would produce an empty image.
+    ZeroSquareSize,
+    /// An image with squares of the given size, in pixels, would be wider
+    /// than PNG allows or could not be allocated.
+    TooLarge(u32),
+}
+
+impl core::fmt::Display for InvalidThemeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::ZeroSquareSize => write!(f, "Cannot render a board with 0-pixel squares."),
+            Self::TooLarge(size) => write!(
+                f,
+                "Cannot render a board with {size}-pixel squares: the image would be too large."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for InvalidThemeError {}
+
+impl<T> Quadboard<T> {
+    /// Renders `self` as a PNG image, returning the encoded file.
+    ///
+    /// The `colour` function is called for each element of `self`, and
+    /// should return the colour of the marker drawn in the middle of its
+    /// square, or `None` to leave the square empty.
+    ///
+    /// # Errors
+    /// Returns an [`InvalidThemeError`] if the square size of `theme` is
+    /// zero, if the side length of the image exceeds the PNG limit of
+    /// 2<sup>31</sup> − 1 pixels, or if the pixel buffer cannot be allocated.
+    pub fn render_png(
+        &self,
+        theme: &Theme,
+        mut colour: impl FnMut(T) -> Option<[u8; 3]>,
+    ) -> Result<Vec<u8>, InvalidThemeError>
+    where
+        Nibble: Into<T>,
+    {
+        let too_large = InvalidThemeError::TooLarge(theme.square_size);
+
+        if theme.square_size == 0 {
+            return Err(InvalidThemeError::ZeroSquareSize);
+        }
+
+        let size = theme.square_size as usize;
+        let side = size.checked_mul(8).ok_or(too_large)?;
+        let side_px = match u32::try_from(side) {
+            Ok(side_px) if side_px <= i32::MAX as u32 => side_px,
+            _ => return Err(too_large),
+        };
+        let len = side
+            .checked_mul(3)
+            .and_then(|row| row.checked_add(1))
+            .and_then(|row| row.checked_mul(side))
+            .ok_or(too_large)?;
+
+        let markers: [Option<[u8; 3]>; 64] =
+            std::array::from_fn(|i| colour(unsafe { self.get_unchecked(i as u8) }));
+
+        // every scanline is preceded by its filter type, which is always 0
+        let mut pixels = Vec::new();
+        pixels.try_reserve_exact(len).map_err(|_| too_large)?;
+        for y in 0..side {
+            pixels.push(0);
+
+            let rank = 7 - y / size;
+            for x in 0..side {
+                let file = x / size;
+                let (dx, dy) = (x % size, y % size);
+
+                let inset = size / 5;
+                let in_marker =
+                    (inset..size - inset).contains(&dx) && (inset..size - inset).contains(&dy);
+
+                let rgb = match markers[8 * rank + file] {
+                    Some(rgb) if in_marker => rgb,
+                    _ if (rank + file).is_multiple_of(2) => theme.dark_square,
+                    _ => theme.light_square,
+                };
+
+                pixels.extend_from_slice(&rgb);
+            }
+        }
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&side_px.to_be_bytes());
+        header.extend_from_slice(&side_px.to_be_bytes());
+        // bit depth 8, colour type 2 (RGB), default compression, filtering, and interlacing
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        write_chunk(&mut png, b"IHDR", &header);
+        write_chunk(&mut png, b"IDAT", &zlib_stored(&pixels));
+        write_chunk(&mut png, b"IEND", &[]);
+        Ok(png)
+    }
+}
+
+/// Appends a PNG chunk with the given type and data to `out`.
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wraps `data` in a zlib stream consisting only of stored deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK_LEN: usize = u16::MAX as usize;
+
+    let mut out = Vec::with_capacity(data.len() + 5 * (data.len() / MAX_BLOCK_LEN + 1) + 6);
+    // deflate with a 32K window and no preset dictionary; 0x7801 is a multiple of 31
+    out.extend_from_slice(&[0x78, 0x01]);
+
+    let mut blocks = data.chunks(MAX_BLOCK_LEN).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+
+    while let Some(block) = blocks.next() {
+        let is_final = blocks.peek().is_none() as u8;
+        let len = block.len() as u16;
+
+        out.push(is_final);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+/// Computes the Adler-32 checksum of `data`.
+fn adler32(data: &[u8]) -> u32 {
+    const MODULUS: u32 = 65521;
+
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + byte as u32) % MODULUS;
+        (a, (b + a) % MODULUS)
+    });
+
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adler32_matches_reference_value() {
+        assert_eq!(adler32(b"Wikipedia"), 0x11E60398);
+    }
+
+    #[test]
+    fn render_png_produces_well_formed_header() {
+        let board = Quadboard::<Nibble>::new_zeroed();
+        let theme = Theme {
+            square_size: 4,
+            ..Theme::default()
+        };
+
+        let png = board
+            .render_png(&theme, |nibble| match nibble.get() {
+                0 => None,
+                _ => Some([0, 0, 0]),
+            })
+            .unwrap();
+
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..20], &32u32.to_be_bytes());
+        assert_eq!(&png[20..24], &32u32.to_be_bytes());
+        assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+    }
+
+    #[test]
+    fn render_png_rejects_invalid_themes() {
+        let board = Quadboard::<Nibble>::new_zeroed();
+        let render = |square_size| {
+            let theme = Theme {
+                square_size,
+                ..Theme::default()
+            };
+
+            board.render_png(&theme, |_| None)
+        };
+
+        assert_eq!(render(0), Err(InvalidThemeError::ZeroSquareSize));
+        // 8 * 2^28 is one pixel wider than PNG allows
+        assert_eq!(render(1 << 28), Err(InvalidThemeError::TooLarge(1 << 28)));
+        assert_eq!(
+            render(u32::MAX / 4),
+            Err(InvalidThemeError::TooLarge(u32::MAX / 4))
+        );
+    }
+}
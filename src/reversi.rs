@@ -0,0 +1,223 @@
+//! Support for reversi (Othello) on the standard 8×8 board.
+//!
+//! Squares are indexed from 0 at a1 to 63 at h8, rank by rank. Moves are
+//! resolved set-wise: the discs flipped by a move are found by shifting the
+//! move's bitboard along each of the eight directions through the opponent's
+//! discs, so no square-by-square walking is required.
+
+use halfling::Nibble;
+
+use crate::codec::EmptySquare;
+use crate::direction::Direction;
+use crate::index::Index;
+use crate::Quadboard;
+
+/// The contents of a single square of a reversi board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(u8)]
+pub enum Square {
+    /// An unoccupied square.
+    #[default]
+    Empty = 0,
+    /// A black disc.
+    Black = 1,
+    /// A white disc.
+    White = 2,
+}
+
+/// The side to which a disc belongs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// The side which moves first.
+    Black,
+    /// The side which moves second.
+    White,
+}
+
+impl Color {
+    /// Returns the opposing color.
+    pub const fn opponent(self) -> Self {
+        match self {
+            Color::Black => Color::White,
+            Color::White => Color::Black,
+        }
+    }
+
+    /// Returns the square holding a disc of this color.
+    pub const fn disc(self) -> Square {
+        match self {
+            Color::Black => Square::Black,
+            Color::White => Square::White,
+        }
+    }
+}
+
+/// The error produced when a [`Nibble`] does not encode a [`Square`].
+#[derive(Debug)]
+pub struct InvalidSquareError(pub Nibble);
+
+impl core::fmt::Display for InvalidSquareError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "The nibble {} does not encode a reversi square.", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidSquareError {}
+
+/// The error produced when attempting to play an illegal move.
+#[derive(Debug, PartialEq, Eq)]
+pub struct IllegalMoveError(pub u8);
+
+impl core::fmt::Display for IllegalMoveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Playing at index {} is not a legal move.", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IllegalMoveError {}
+
+impl From<Square> for Nibble {
+    fn from(value: Square) -> Self {
+        unsafe { Nibble::new_unchecked(value as u8) }
+    }
+}
+
+impl TryFrom<Nibble> for Square {
+    type Error = InvalidSquareError;
+
+    fn try_from(value: Nibble) -> Result<Self, Self::Error> {
+        match value.get() {
+            0 => Ok(Square::Empty),
+            1 => Ok(Square::Black),
+            2 => Ok(Square::White),
+            _ => Err(InvalidSquareError(value)),
+        }
+    }
+}
+
+impl EmptySquare for Square {}
+
+/// Returns the standard starting position, with two discs of each color
+/// crossed in the centre of the board.
+pub fn start_position() -> Quadboard<Square> {
+    let mut board = Quadboard::new_zeroed();
+    board
+        .inner
+        .fill_masked((1 << 27) | (1 << 36), Square::White.into());
+    board
+        .inner
+        .fill_masked((1 << 28) | (1 << 35), Square::Black.into());
+    board
+}
+
+/// Returns the mask of the squares holding a disc of the given color.
+pub fn discs(board: &Quadboard<Square>, color: Color) -> u64 {
+    board.mask_of(color.disc())
+}
+
+/// Returns the mask of the discs that would be flipped if `color` played at
+/// `index`, which is empty exactly when the move is illegal.
+pub fn flips(board: &Quadboard<Square>, index: Index, color: Color) -> u64 {
+    let own = discs(board, color);
+    let opponent = discs(board, color.opponent());
+    let placed = 1u64 << index.get();
+
+    if (own | opponent) & placed != 0 {
+        return 0;
+    }
+
+    Direction::ALL.iter().fold(0, |flipped, &direction| {
+        let mut line = 0;
+        let mut frontier = direction.shift(placed) & opponent;
+
+        // extend the line through the opponent's discs until it either
+        // reaches one of our own discs (a capture) or falls off the board
+        while frontier != 0 {
+            line |= frontier;
+            let next = direction.shift(frontier);
+
+            if next & own != 0 {
+                return flipped | line;
+            }
+
+            frontier = next & opponent;
+        }
+
+        flipped
+    })
+}
+
+/// Returns the mask of the squares at which `color` may legally play.
+pub fn legal_moves(board: &Quadboard<Square>, color: Color) -> u64 {
+    let empty = board.mask_of(Square::Empty);
+
+    (0..64u8)
+        .filter(|i| empty & (1 << i) != 0)
+        .filter(|&i| flips(board, unsafe { Index::new_unchecked(i) }, color) != 0)
+        .fold(0, |acc, i| acc | (1 << i))
+}
+
+/// Plays a disc of the given color at `index` and flips the captured discs,
+/// returning the mask of the flipped discs.
+pub fn apply_move(
+    board: &mut Quadboard<Square>,
+    index: Index,
+    color: Color,
+) -> Result<u64, IllegalMoveError> {
+    match flips(board, index, color) {
+        0 => Err(IllegalMoveError(index.get())),
+        flipped => {
+            let placed = 1u64 << index.get();
+            board
+                .inner
+                .fill_masked(flipped | placed, color.disc().into());
+            Ok(flipped)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn start_position_has_four_legal_moves() {
+        let board = start_position();
+
+        assert_eq!(discs(&board, Color::Black).count_ones(), 2);
+        assert_eq!(discs(&board, Color::White).count_ones(), 2);
+
+        // d3, c4, f5, and e6
+        let expected = (1 << 19) | (1 << 26) | (1 << 37) | (1 << 44);
+        assert_eq!(legal_moves(&board, Color::Black), expected);
+    }
+
+    #[test]
+    fn apply_move_flips_captured_lines() {
+        let mut board = start_position();
+
+        // black plays d3, capturing the white disc on d4
+        let flipped = apply_move(&mut board, 19.try_into().unwrap(), Color::Black).unwrap();
+        assert_eq!(flipped, 1 << 27);
+        assert_eq!(discs(&board, Color::Black).count_ones(), 4);
+        assert_eq!(discs(&board, Color::White).count_ones(), 1);
+
+        assert_eq!(
+            apply_move(&mut board, 0.try_into().unwrap(), Color::White),
+            Err(IllegalMoveError(0))
+        );
+    }
+
+    #[test]
+    fn captures_do_not_wrap_across_files() {
+        let mut board = Quadboard::new_zeroed();
+        // a white disc on h1 followed by a black disc on a2 must not
+        // count as a horizontal line when black plays at g1
+        board.set(7.try_into().unwrap(), Square::White);
+        board.set(8.try_into().unwrap(), Square::Black);
+
+        assert_eq!(flips(&board, 6.try_into().unwrap(), Color::Black), 0);
+    }
+}
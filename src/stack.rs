@@ -0,0 +1,154 @@
+//! A contiguous stack of boards, for make/unmake style search.
+//!
+//! Search routines typically keep one board per ply, copying the current
+//! board before each move and discarding it when the move is unmade. A
+//! [`QuadboardStack`] stores these boards contiguously in a single buffer,
+//! so that the boards for consecutive plies are adjacent in memory and the
+//! buffer can be allocated once for the maximum search depth.
+
+use alloc::vec::Vec;
+
+use crate::Quadboard;
+
+/// A stack of [`Quadboard`]s stored contiguously, with the most recently
+/// pushed board on top.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QuadboardStack<T> {
+    boards: Vec<Quadboard<T>>,
+}
+
+impl<T> Default for QuadboardStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> QuadboardStack<T> {
+    /// Creates a new, empty [`QuadboardStack`].
+    pub const fn new() -> Self {
+        Self { boards: Vec::new() }
+    }
+
+    /// Creates a new, empty [`QuadboardStack`] with space for at least
+    /// `capacity` boards before reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            boards: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Creates a new [`QuadboardStack`] holding only `board`, with space for
+    /// at least `capacity` boards before reallocating.
+    pub fn with_root(board: Quadboard<T>, capacity: usize) -> Self {
+        let mut stack = Self::with_capacity(capacity.max(1));
+        stack.push(board);
+        stack
+    }
+
+    /// Returns the number of boards in `self`.
+    pub fn len(&self) -> usize {
+        self.boards.len()
+    }
+
+    /// Returns `true` if `self` holds no boards.
+    pub fn is_empty(&self) -> bool {
+        self.boards.is_empty()
+    }
+
+    /// Returns the number of boards `self` can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.boards.capacity()
+    }
+
+    /// Reserves space for at least `additional` more boards.
+    pub fn reserve(&mut self, additional: usize) {
+        self.boards.reserve(additional);
+    }
+
+    /// Pushes `board` onto the top of `self`.
+    pub fn push(&mut self, board: Quadboard<T>) {
+        self.boards.push(board);
+    }
+
+    /// Pushes a copy of the top board onto `self`, and returns a mutable
+    /// reference to the new top board.
+    ///
+    /// # Panics
+    /// Panics if `self` is empty.
+    pub fn push_copy(&mut self) -> &mut Quadboard<T> {
+        let top = self
+            .top()
+            .map(|board| Quadboard::from_raw(board.inner))
+            .expect("cannot copy the top of an empty QuadboardStack");
+
+        self.boards.push(top);
+        self.boards.last_mut().unwrap()
+    }
+
+    /// Removes and returns the top board of `self`, or returns `None` if
+    /// `self` is empty.
+    pub fn pop(&mut self) -> Option<Quadboard<T>> {
+        self.boards.pop()
+    }
+
+    /// Returns a reference to the top board of `self`.
+    pub fn top(&self) -> Option<&Quadboard<T>> {
+        self.boards.last()
+    }
+
+    /// Returns a mutable reference to the top board of `self`.
+    pub fn top_mut(&mut self) -> Option<&mut Quadboard<T>> {
+        self.boards.last_mut()
+    }
+
+    /// Removes every board above the first `len`, so that the board pushed
+    /// at depth `len - 1` is on top.
+    pub fn truncate(&mut self, len: usize) {
+        self.boards.truncate(len);
+    }
+
+    /// Removes every board from `self`, keeping its allocation.
+    pub fn clear(&mut self) {
+        self.boards.clear();
+    }
+
+    /// Returns the boards of `self` as a slice, from the bottom of the stack
+    /// to the top.
+    pub fn as_slice(&self) -> &[Quadboard<T>] {
+        &self.boards
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::Index;
+    use halfling::Nibble;
+
+    #[test]
+    fn push_copy_and_pop_restore_earlier_boards() {
+        let mut stack = QuadboardStack::with_root(Quadboard::<Nibble>::new_zeroed(), 8);
+        assert!(stack.capacity() >= 8);
+
+        stack.push_copy().set(Index::MIN, Nibble::THREE);
+        stack.push_copy().set(Index::MAX, Nibble::FIVE);
+        assert_eq!(stack.len(), 3);
+
+        let top = stack.top().unwrap();
+        assert_eq!(
+            (top.get(Index::MIN), top.get(Index::MAX)),
+            (Nibble::THREE, Nibble::FIVE)
+        );
+
+        stack.pop();
+        assert_eq!(stack.top().unwrap().get(Index::MAX), Nibble::ZERO);
+        stack.top_mut().unwrap().set(Index::MAX, Nibble::ONE);
+        assert_eq!(stack.as_slice()[1].get(Index::MAX), Nibble::ONE);
+
+        stack.truncate(1);
+        assert_eq!(stack.top(), Some(&Quadboard::new_zeroed()));
+        stack.clear();
+        assert!(stack.is_empty());
+        assert!(stack.pop().is_none());
+    }
+}
@@ -0,0 +1,140 @@
+//! Zobrist hashing of quadboards.
+//!
+//! A [`ZobristTable`] assigns a random key to every pair of a square and a
+//! nonzero [`Nibble`], and the hash of a board is the XOR of the keys of its
+//! elements. The zero nibble always has the key 0, so the empty board hashes
+//! to 0 and placing or removing a single element is one [`ZobristTable::toggle`].
+
+use halfling::Nibble;
+
+use crate::index::Index;
+use crate::raw_quadboard::RawQuadboard;
+use crate::Quadboard;
+
+/// The seed used by [`ZobristTable::default`].
+const DEFAULT_SEED: u64 = 0x5155_4144_424F_4152;
+
+/// A table of Zobrist keys, one for each square and nonzero [`Nibble`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZobristTable {
+    keys: [[u64; 16]; 64],
+}
+
+impl Default for ZobristTable {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEED)
+    }
+}
+
+impl ZobristTable {
+    /// Generates a new table from `seed` with the splitmix64 generator, so
+    /// that the same seed always produces the same keys.
+    pub const fn new(seed: u64) -> Self {
+        let mut keys = [[0; 16]; 64];
+        let mut state = seed;
+        let mut square = 0;
+
+        while square < 64 {
+            let mut value = 1;
+
+            while value < 16 {
+                state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+                let mut z = state;
+                z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+                keys[square][value] = z ^ (z >> 31);
+                value += 1;
+            }
+
+            square += 1;
+        }
+
+        Self { keys }
+    }
+
+    /// Returns the key of `value` at `index`.
+    #[inline(always)]
+    pub const fn key(&self, index: Index, value: Nibble) -> u64 {
+        self.keys[index.get() as usize][value.get() as usize]
+    }
+
+    /// Computes the hash of `board` from scratch.
+    pub fn hash_raw(&self, board: &RawQuadboard) -> u64 {
+        board
+            .to_nibble_bytes()
+            .iter()
+            .zip(self.keys.iter())
+            .fold(0, |hash, (&value, keys)| hash ^ keys[value as usize])
+    }
+
+    /// Computes the hash of `board` from scratch.
+    #[inline(always)]
+    pub fn hash<T>(&self, board: &Quadboard<T>) -> u64 {
+        self.hash_raw(board.as_raw_quadboard())
+    }
+
+    /// Updates `hash` to account for `value` being added to or removed from
+    /// the square at `index`.
+    ///
+    /// To account for overwriting an element, toggle both the old and the
+    /// new values; toggling a zero-encoded value does nothing.
+    #[inline(always)]
+    pub fn toggle<T>(&self, hash: u64, index: Index, value: T) -> u64
+    where
+        T: Into<Nibble>,
+    {
+        hash ^ self.key(index, value.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_nibble_has_zero_key() {
+        let table = ZobristTable::default();
+
+        for index in 0..64u8 {
+            assert_eq!(table.key(index.try_into().unwrap(), Nibble::ZERO), 0);
+        }
+
+        assert_eq!(table.hash_raw(&RawQuadboard::default()), 0);
+    }
+
+    #[test]
+    fn keys_are_distinct() {
+        let table = ZobristTable::new(17);
+        let mut keys: Vec<u64> = table
+            .keys
+            .iter()
+            .flat_map(|keys| keys[1..].iter().copied())
+            .collect();
+        keys.sort_unstable();
+        keys.dedup();
+
+        assert_eq!(keys.len(), 64 * 15);
+        assert_ne!(ZobristTable::new(17), ZobristTable::new(18));
+    }
+
+    #[test]
+    fn toggle_agrees_with_hash() {
+        let table = ZobristTable::default();
+        let (a, b) = (12.try_into().unwrap(), 28.try_into().unwrap());
+        let mut board = Quadboard::<Nibble>::new_zeroed();
+        board.set(a, Nibble::THREE);
+        let mut hash = table.hash(&board);
+
+        // move the element from a to b, overwriting nothing
+        board.relocate(a, b, Nibble::ZERO);
+        hash = table.toggle(hash, a, Nibble::THREE);
+        hash = table.toggle(hash, b, Nibble::THREE);
+        assert_eq!(hash, table.hash(&board));
+
+        // overwrite the element at b
+        board.set(b, Nibble::NINE);
+        hash = table.toggle(hash, b, Nibble::THREE);
+        hash = table.toggle(hash, b, Nibble::NINE);
+        assert_eq!(hash, table.hash(&board));
+    }
+}